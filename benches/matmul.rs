@@ -0,0 +1,39 @@
+//! Reports the time taken by `matmul` and `matmul_blocked` on the same product.
+//! Run it with `cargo bench --bench matmul`, optionally passing the size of the
+//! matrices and the block size, e.g. `cargo bench --bench matmul -- 1024 64`.
+use mat_lib::dense::Dense;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: u32 = 5;
+
+/// Returns the fastest of `RUNS` runs of `f`, which is the least affected by noise.
+fn time<F: FnMut() -> Option<Dense>>(mut f: F) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let args: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+
+    let n = args.first().copied().unwrap_or(512);
+    let block = args.get(1).copied().unwrap_or(64);
+
+    let a = Dense::rand(n, n);
+    let b = Dense::rand(n, n);
+
+    let naive = time(|| a.matmul(&b));
+    let blocked = time(|| a.matmul_blocked(&b, block));
+
+    println!("{n}x{n} matmul:              {naive:?}");
+    println!("{n}x{n} matmul_blocked({block}): {blocked:?}");
+}
@@ -0,0 +1,175 @@
+//! A banded matrix implementation where every element further than `B` positions
+//! from the main diagonal is implicitly null. It generalizes the diagonal matrix,
+//! which is the case `B = 0`, and is often used for tridiagonal systems where `B = 1`.
+use crate::diag::DiagImplTraits;
+use crate::mats::display::grid;
+use std::mem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Banded<T, const N: usize, const M: usize, const B: usize> {
+    // Row `i` stores the entries `(i, i - B)..=(i, i + B)` contiguously,
+    // including the slots that fall outside of the matrix.
+    data: Vec<T>,
+    // self.zero is used to return a reference to
+    // the value 0 when the index is out of the band.
+    zero: T,
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize, const B: usize> Banded<T, N, M, B> {
+    /// Initializes a new `N x M` matrix filled with zeros.
+    pub fn zeros() -> Self {
+        let zero = T::from(0);
+        let data = vec![zero; N * (2 * B + 1)];
+        Self { data, zero }
+    }
+
+    /// Returns the position in `self.data` of the entry at `(i, j)`,
+    /// or `None` if it is out of the band.
+    fn offset(&self, (i, j): (usize, usize)) -> Option<usize> {
+        (i.abs_diff(j) <= B).then(|| i * (2 * B + 1) + j + B - i)
+    }
+
+    /// Returns a reference to the entry at the given `idx: (i, j)`.
+    /// If the given index is out of bounds returns the `None` variant.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::banded::Banded;
+    /// let mut mat = Banded::<f32, 3, 3, 1>::zeros();
+    /// mat.set((0, 1), 2.0);
+    /// assert_eq!(Some(&2.0), mat.get((0, 1)));
+    /// assert_eq!(Some(&0.0), mat.get((0, 2)));
+    /// assert!(mat.get((3, 0)).is_none());
+    /// ```
+    pub fn get(&self, idx @ (i, j): (usize, usize)) -> Option<&T> {
+        if i >= N || j >= M {
+            return None;
+        }
+
+        Some(self.offset(idx).map_or(&self.zero, |k| &self.data[k]))
+    }
+
+    /// Returns a mutable reference to the entry at the given `idx: (i, j)`.
+    /// If the given index is out of bounds or out of the band returns the `None` variant.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::banded::Banded;
+    /// let mut mat = Banded::<f32, 3, 3, 1>::zeros();
+    ///
+    /// if let Some(num) = mat.get_mut((1, 0)) {
+    ///     *num = 1.0;
+    /// }
+    ///
+    /// assert_eq!(1.0, mat[(1, 0)]);
+    /// assert_eq!(None, mat.get_mut((2, 0)));
+    /// ```
+    pub fn get_mut(&mut self, idx @ (i, j): (usize, usize)) -> Option<&mut T> {
+        if i >= N || j >= M {
+            return None;
+        }
+
+        self.offset(idx).map(|k| &mut self.data[k])
+    }
+
+    /// Sets `val` to the given `idx: (i, j)` in the matrix
+    /// and returns the previous value.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::banded::Banded;
+    /// let mut mat = Banded::<i32, 3, 3, 1>::zeros();
+    /// assert_eq!(Some(0), mat.set((2, 1), 7));
+    /// assert_eq!(7, mat[(2, 1)]);
+    ///
+    /// // Out of the band.
+    /// assert_eq!(None, mat.set((2, 0), 8));
+    /// ```
+    pub fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
+        self.get_mut(idx).map(|num| mem::replace(num, val))
+    }
+
+    /// Returns the shape of the matrix in the format `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (N, M)
+    }
+
+    /// Applies the given function `f` to every entry in the band of the matrix.
+    /// Entries out of the band are left as zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::banded::Banded;
+    /// let mut mat = Banded::<i32, 2, 2, 1>::zeros();
+    /// mat.apply(|e| e + 1);
+    /// assert_eq!(1, mat[(0, 1)]);
+    /// ```
+    pub fn apply<F: Fn(T) -> T>(&mut self, f: F) -> &mut Self {
+        for i in 0..N {
+            for j in i.saturating_sub(B)..(i + B + 1).min(M) {
+                let k = i * (2 * B + 1) + j + B - i;
+                self.data[k] = f(self.data[k]);
+            }
+        }
+
+        self
+    }
+
+    /// Formats the matrix with `precision` decimals, see `display::grid` for the layout.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::banded::Banded;
+    /// let mut mat = Banded::<i32, 2, 3, 0>::zeros();
+    /// mat.set((1, 1), 5);
+    /// assert_eq!("[ 0 0 0 ]\n[ 0 5 0 ]", mat.to_string_with(0, 0));
+    /// ```
+    pub fn to_string_with(&self, precision: usize, width: usize) -> String {
+        grid(N, M, width, |i, j| format!("{:.precision$}", self[(i, j)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tridiagonal() {
+        let mut mat = Banded::<f64, 4, 4, 1>::zeros();
+        for i in 0..4 {
+            mat.set((i, i), 2.0);
+            if i > 0 {
+                mat.set((i, i - 1), -1.0);
+                mat.set((i - 1, i), -1.0);
+            }
+        }
+
+        assert_eq!((4, 4), mat.shape());
+        assert_eq!(2.0, mat[(3, 3)]);
+        assert_eq!(-1.0, mat[(2, 1)]);
+        assert_eq!(-1.0, mat[(2, 3)]);
+
+        // Out of band entries read as zero but can't be written.
+        assert_eq!(0.0, mat[(0, 2)]);
+        assert_eq!(0.0, mat[(3, 0)]);
+        assert_eq!(None, mat.set((0, 3), 1.0));
+        assert_eq!(None, mat.get((4, 4)));
+
+        mat.apply(|e| e * 2.0);
+        assert_eq!(-2.0, mat[(0, 1)]);
+        assert_eq!(0.0, mat[(0, 2)]);
+    }
+
+    #[test]
+    fn rectangular() {
+        let mut mat = Banded::<i32, 2, 5, 2>::zeros();
+        assert_eq!(Some(0), mat.set((0, 2), 3));
+        assert_eq!(Some(0), mat.set((1, 3), 4));
+        assert_eq!(None, mat.set((0, 3), 5));
+        assert_eq!(None, mat.set((1, 5), 5));
+
+        mat.apply(|e| e + 1);
+        assert_eq!("[ 1 1 4 0 0 ]\n[ 1 1 1 5 0 ]", mat.to_string());
+    }
+}
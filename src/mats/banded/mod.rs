@@ -0,0 +1,4 @@
+mod mat;
+mod traits;
+
+pub use mat::*;
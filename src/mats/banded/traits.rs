@@ -0,0 +1,66 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use super::mat::Banded;
+use crate::diag::DiagImplTraits;
+use crate::matrix::Matrix;
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize, const B: usize> Index<(usize, usize)>
+    for Banded<T, N, M, B>
+{
+    type Output = T;
+    fn index(&self, idx: (usize, usize)) -> &Self::Output {
+        self.get(idx).expect("Index out of range")
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize, const B: usize> IndexMut<(usize, usize)>
+    for Banded<T, N, M, B>
+{
+    fn index_mut(&mut self, idx: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(idx).expect("IndexMut out of range")
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize, const B: usize> Display
+    for Banded<T, N, M, B>
+{
+    /// Formats the matrix as `to_string_with(4, 0)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(4, 0))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize, const B: usize> Matrix<T>
+    for Banded<T, N, M, B>
+{
+    /// The shape of a `Banded` is fixed by `N` and `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(n, m)` differs from `(N, M)`.
+    fn zeros(n: usize, m: usize) -> Self {
+        assert_eq!((N, M), (n, m), "Shape does not match the matrix dimensions");
+        Banded::zeros()
+    }
+
+    fn get(&self, idx: (usize, usize)) -> Option<&T> {
+        Banded::get(self, idx)
+    }
+
+    fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+        Banded::get_mut(self, idx)
+    }
+
+    fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
+        Banded::set(self, idx, val)
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        Banded::shape(self)
+    }
+}
@@ -0,0 +1,80 @@
+//! An accumulator for summing a stream of `Dense` matrices with the same shape.
+use super::mat::Dense;
+
+/// Keeps the running sum of a stream of `Dense` matrices
+/// reusing the same buffer for every addition.
+///
+/// # Usage
+/// ```
+/// use mat_lib::dense::*;
+/// let mut acc = DenseAccumulator::new(1, 2);
+/// acc.add_assign(&Dense::from_str("1 2").unwrap()).unwrap();
+/// acc.add_assign(&Dense::from_str("3 4").unwrap()).unwrap();
+/// assert_eq!(Dense::from_str("4 6").unwrap(), acc.finish());
+/// ```
+#[derive(Debug)]
+pub struct DenseAccumulator {
+    sum: Dense,
+}
+
+impl DenseAccumulator {
+    /// Initializes a new accumulator for `n x m` matrices starting from zero.
+    pub fn new(n: usize, m: usize) -> Self {
+        let sum = Dense::zeros(n, m);
+        Self { sum }
+    }
+
+    /// Adds `other` to the running sum.
+    ///
+    /// # Errors
+    /// This method will return the Err variant if the shape of `other`
+    /// differs from the shape of the accumulated matrices.
+    pub fn add_assign(&mut self, other: &Dense) -> Result<(), &'static str> {
+        let (n, m) = self.sum.shape();
+        if other.shape() != (n, m) {
+            return Err("Invalid shape for matrix");
+        }
+
+        for i in 0..n {
+            for j in 0..m {
+                self.sum[(i, j)] += other[(i, j)];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the accumulator returning the accumulated sum.
+    pub fn finish(self) -> Dense {
+        self.sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulate() {
+        let mats = [
+            Dense::from_str("1 2 3; 4 5 6").unwrap(),
+            Dense::from_str("-1 0 2; 0.5 1 1").unwrap(),
+            Dense::from_str("10 20 30; 40 50 60").unwrap(),
+        ];
+
+        let mut acc = DenseAccumulator::new(2, 3);
+        for mat in &mats {
+            acc.add_assign(mat).unwrap();
+        }
+
+        let res = Dense::from_fn(2, 3, |i, j| mats.iter().map(|mat| mat[(i, j)]).sum());
+        assert_eq!(res, acc.finish());
+    }
+
+    #[test]
+    fn shape_mismatch() {
+        let mut acc = DenseAccumulator::new(2, 2);
+        assert!(acc.add_assign(&Dense::zeros(2, 3)).is_err());
+        assert_eq!(Dense::zeros(2, 2), acc.finish());
+    }
+}
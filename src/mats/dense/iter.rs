@@ -0,0 +1,121 @@
+//! Iteration over the entries of a `Dense` matrix alongside their coordinates.
+use std::iter::{Enumerate, FusedIterator};
+use std::slice;
+use std::vec;
+
+use super::mat::Dense;
+use super::DenseImplTraits;
+
+/// An owning iterator over the entries of a `Dense` matrix in row-major order,
+/// yielding every entry alongside its index `(i, j)`.
+#[derive(Debug, Clone)]
+pub struct IntoIter<T> {
+    inner: Enumerate<vec::IntoIter<T>>,
+    m: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = ((usize, usize), T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, val) = self.inner.next()?;
+        Some(((k / self.m, k % self.m), val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// An iterator over the entries of a `Dense` matrix in row-major order,
+/// yielding a copy of every entry alongside its index `(i, j)`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    inner: Enumerate<slice::Iter<'a, T>>,
+    m: usize,
+}
+
+impl<T: Copy> Iterator for Iter<'_, T> {
+    type Item = ((usize, usize), T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, &val) = self.inner.next()?;
+        Some(((k / self.m, k % self.m), val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for Iter<'_, T> {}
+impl<T: Copy> FusedIterator for Iter<'_, T> {}
+
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> IntoIterator for Dense<T> {
+    type Item = ((usize, usize), T);
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the matrix returning its entries in row-major order.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let entries: Vec<_> = mat.into_iter().collect();
+    /// assert_eq!(((1, 0), 3), entries[2]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        let (_, m) = self.shape();
+        let inner = self.data.into_iter().enumerate();
+        IntoIter { inner, m }
+    }
+}
+
+#[allow(private_bounds)]
+impl<'a, T: DenseImplTraits> IntoIterator for &'a Dense<T> {
+    type Item = ((usize, usize), T);
+    type IntoIter = Iter<'a, T>;
+
+    /// Returns the entries of the matrix in row-major order.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let sum: i32 = (&mat).into_iter().map(|((i, j), val)| (i + j) as i32 * val).sum();
+    /// assert_eq!(2 + 3 + 8, sum);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        let (_, m) = self.shape();
+        let inner = self.data.iter().enumerate();
+        Iter { inner, m }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dense::*;
+
+    #[test]
+    fn into_iter() {
+        let mat = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+        let expected = vec![((0, 0), 1), ((0, 1), 2), ((1, 0), 3), ((1, 1), 4)];
+
+        let mut count = 0;
+        for (idx, val) in &mat {
+            assert_eq!(expected[count], (idx, val));
+            count += 1;
+        }
+        assert_eq!(4, count);
+
+        let iter = mat.into_iter();
+        assert_eq!(4, iter.len());
+        assert_eq!(expected, iter.collect::<Vec<_>>());
+
+        assert_eq!(0, Dense::<i32>::zeros(3, 0).into_iter().count());
+    }
+}
@@ -1,26 +1,125 @@
 //! A dense matrix implementation where every element is explicitly stored in memeory.
 //! It is often used when majority of entries are non-zero for computing operations
 //! between matrices such as addition and multiplication.
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
 use std::mem;
+use std::ops::Range;
 pub use std::str::FromStr;
 
+use crate::mats::display::grid;
+use crate::sparse::Sparse;
 use crate::Entry;
 
-#[derive(Debug, PartialEq)]
-pub struct Dense {
-    data: Vec<Entry>,
+use super::DenseImplTraits;
+
+/// Tolerance under which a computed value is considered to be zero.
+const EPSILON: Entry = 1e-5;
+
+/// Computes the dot product between two vectors of the same length.
+fn dot(u: &[Entry], v: &[Entry]) -> Entry {
+    u.iter().zip(v).map(|(a, b)| a * b).sum()
+}
+
+/// Computes the eigendecomposition of the `n x n` symmetric matrix stored row-major in `a`
+/// with the cyclic Jacobi method, returning the eigenvalues alongside the row-major entries
+/// of the orthogonal matrix whose columns are the corresponding eigenvectors. Sweeps of
+/// rotations are applied until the norm of the off-diagonal entries drops below `tol`
+/// times the norm of the matrix. Returns `None` if it does not converge in `iters` sweeps.
+fn eigen_symmetric(
+    mut a: Vec<f64>,
+    n: usize,
+    iters: usize,
+    tol: f64,
+) -> Option<(Vec<f64>, Vec<f64>)> {
+    let mut v: Vec<f64> = (0..n * n).map(|k| (k / n == k % n) as u8 as f64).collect();
+    let norm = a.iter().map(|e| e * e).sum::<f64>().sqrt();
+    let off = |a: &[f64]| {
+        let sum: f64 = (0..n * n)
+            .filter(|k| k / n != k % n)
+            .map(|k| a[k] * a[k])
+            .sum();
+        sum.sqrt()
+    };
+
+    for _ in 0..=iters {
+        if off(&a) <= tol * norm {
+            let values = (0..n).map(|i| a[i * n + i]).collect();
+            return Some((values, v));
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq == 0.0 {
+                    continue;
+                }
+
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                // A ← Jᵀ·A·J and V ← V·J for the rotation J in the (p, q) plane.
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dense<T = Entry> {
+    pub(super) data: Vec<T>,
     n: usize,
     m: usize,
 }
 
-impl FromStr for Dense {
+/// A `Dense` matrix of `f32` entries.
+pub type DenseF32 = Dense<f32>;
+
+/// Constructors of a `Dense` matrix for any entry type `T`.
+///
+/// `Dense` provides the same constructors as inherent functions for `f32` entries,
+/// which is what `Dense::<i32>::zeros(n, m)` resolves to when the entry type is not given.
+/// Other entry types go through this trait, e.g. `Dense::<i32>::zeros(n, m)`.
+pub trait DenseConstructors<T>: Sized {
+    /// Initializes a new `n x m` matrix filled with zeros.
+    fn zeros(n: usize, m: usize) -> Self;
+
+    /// Returns the `n x n` identity matrix.
+    fn identity(n: usize) -> Self;
+
+    /// Initializes a new `n x m` matrix where every entry `(i, j)` is set to `f(i, j)`.
+    fn from_fn<F: Fn(usize, usize) -> T>(n: usize, m: usize, f: F) -> Self;
+}
+
+#[allow(private_bounds)]
+impl<T: DenseImplTraits + FromStr> FromStr for Dense<T> {
     type Err = &'static str;
     /// Create a `Dense` matrix from a string
     ///
     /// # Syntax
     ///
-    /// Numbers are read as `Entry` type numbers and lines are separated by ';'.
+    /// Numbers are read as `T` type numbers and lines are separated by ';'.
     /// Matrices should have the same number of entries throughout every row to be valid.
     ///
     /// # Examples
@@ -45,21 +144,26 @@ impl FromStr for Dense {
     }
 }
 
-impl Dense {
-    /// Initializes a new `N x M` matrix filled with zeros.
-    pub fn zeros(n: usize, m: usize) -> Self {
-        let data = vec![0.0; n * m];
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> DenseConstructors<T> for Dense<T> {
+    fn zeros(n: usize, m: usize) -> Self {
+        let data = vec![T::from(0); n * m];
         Self { data, n, m }
     }
 
-    /// Initializes a new `N x M` matrix filled with random values.
-    pub fn rand(n: usize, m: usize) -> Self {
-        let mut rand_gen = rand::thread_rng();
-        let data = (0..n * m).map(|_| rand_gen.gen()).collect();
+    fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| T::from((i == j) as u8))
+    }
+
+    fn from_fn<F: Fn(usize, usize) -> T>(n: usize, m: usize, f: F) -> Self {
+        let data = (0..n * m).map(|k| f(k / m, k % m)).collect();
         Self { data, n, m }
     }
+}
 
-    /// Returns a reference to the `Entry` at the given `idx: (i, j)`.
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Dense<T> {
+    /// Returns a reference to the entry at the given `idx: (i, j)`.
     /// If the given index is out of bounds returns the `None` variant.
     ///
     /// # Usage
@@ -70,11 +174,11 @@ impl Dense {
     /// assert!(mat.get((2, 0)).is_none());
     /// ```
     #[allow(unused_variables)]
-    pub fn get(&self, idx @ (i, j): (usize, usize)) -> Option<&Entry> {
+    pub fn get(&self, idx @ (i, j): (usize, usize)) -> Option<&T> {
         self.data.get(i * self.m + j)
     }
 
-    /// Returns a mutable reference to the `Entry` at the given `idx: :(i, j)`.
+    /// Returns a mutable reference to the entry at the given `idx: :(i, j)`.
     /// If the given index is out of bounds returns the `None` variant.
     ///
     /// # Usage
@@ -93,7 +197,7 @@ impl Dense {
     /// assert_eq!(1.0, mat[(0, 0)]);
     /// ```
     #[allow(unused_variables)]
-    pub fn get_mut(&mut self, idx @ (i, j): (usize, usize)) -> Option<&mut Entry> {
+    pub fn get_mut(&mut self, idx @ (i, j): (usize, usize)) -> Option<&mut T> {
         self.data.get_mut(i * self.m + j)
     }
 
@@ -111,7 +215,7 @@ impl Dense {
     /// let prev = mat.set((3, 3), 8.0);
     /// assert_eq!(None, prev);
     /// ````
-    pub fn set(&mut self, idx: (usize, usize), val: Entry) -> Option<Entry> {
+    pub fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
         self.get_mut(idx).map(|num| mem::replace(num, val))
     }
 
@@ -125,6 +229,18 @@ impl Dense {
         n == m
     }
 
+    /// Returns `true` if the matrix is square and equal to its transpose.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// assert!(Dense::<i32>::from_str("1 2; 2 3").unwrap().is_symmetric());
+    /// assert!(!Dense::<i32>::from_str("1 2; 0 3").unwrap().is_symmetric());
+    /// ```
+    pub fn is_symmetric(&self) -> bool {
+        self.is_square() && (0..self.n).all(|i| (0..i).all(|j| self[(i, j)] == self[(j, i)]))
+    }
+
     /// Computes the multiplication of the given matrix and `rhs` in-place.
     ///
     /// # Usage
@@ -136,7 +252,7 @@ impl Dense {
     /// let res = Dense::from_str("2 4 6").unwrap();
     /// assert_eq!(res, mat);
     /// ````
-    pub fn scalar_mul(&mut self, rhs: Entry) -> &mut Self {
+    pub fn scalar_mul(&mut self, rhs: T) -> &mut Self {
         self.apply(|e| e * rhs)
     }
 
@@ -151,16 +267,3607 @@ impl Dense {
     /// let res = Dense::from_str("6; 7; 8").unwrap();
     /// assert_eq!(res, mat);
     /// ```
-    pub fn apply<F: Fn(Entry) -> Entry>(&mut self, f: F) -> &mut Self {
+    pub fn apply<F: Fn(T) -> T>(&mut self, f: F) -> &mut Self {
         self.data.iter_mut().for_each(|e| *e = f(*e));
         self
     }
 
-    pub fn det(&self) -> Option<Entry> {
-        todo!()
+    /// Applies the given function `f` to every entry in the matrix,
+    /// passing the index `(i, j)` of the entry alongside its value.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// mat.apply_indexed(|(i, j), v| if i > j { 0.0 } else { v });
+    ///
+    /// let res = Dense::from_str("1 2; 0 4").unwrap();
+    /// assert_eq!(res, mat);
+    /// ```
+    pub fn apply_indexed<F: Fn((usize, usize), T) -> T>(&mut self, f: F) -> &mut Self {
+        let m = self.m;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .for_each(|(k, e)| *e = f((k / m, k % m), *e));
+        self
     }
 
-    pub fn inv(&mut self) -> Option<&mut Self> {
-        todo!();
+    /// Computes the element-wise addition between the given matrix and `rhs`.
+    /// Returns `None` if both matrices don't have the same shape.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2").unwrap();
+    /// let b = Dense::<i32>::from_str("3 4").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("4 6").ok(), a.add(&b));
+    /// ```
+    pub fn add(&self, rhs: &Self) -> Option<Self> {
+        if self.shape() != rhs.shape() {
+            return None;
+        }
+
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(&a, &b)| a + b)
+            .collect();
+        Some(Self { data, ..*self })
+    }
+
+    /// Computes the element-wise subtraction between the given matrix and `rhs`.
+    /// Returns `None` if both matrices don't have the same shape.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2").unwrap();
+    /// let b = Dense::<i32>::from_str("3 4").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("-2 -2").ok(), a.sub(&b));
+    /// ```
+    pub fn sub(&self, rhs: &Self) -> Option<Self> {
+        if self.shape() != rhs.shape() {
+            return None;
+        }
+
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(&a, &b)| a - b)
+            .collect();
+        Some(Self { data, ..*self })
+    }
+
+    /// Computes the matrix product between the given matrix and `rhs`.
+    /// Returns `None` if the number of columns of the matrix differs
+    /// from the number of rows of `rhs`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::<i32>::from_str("5; 6").unwrap();
+    ///
+    /// let res = Dense::<i32>::from_str("17; 39").unwrap();
+    /// assert_eq!(Some(res), a.matmul(&b));
+    /// assert_eq!(None, b.matmul(&a));
+    /// ```
+    pub fn matmul(&self, rhs: &Self) -> Option<Self> {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        if m != p {
+            return None;
+        }
+
+        let mut res = Self::zeros(n, q);
+        for i in 0..n {
+            for k in 0..m {
+                let a = self.data[i * m + k];
+                for j in 0..q {
+                    let acc = &mut res.data[i * q + j];
+                    *acc = *acc + a * rhs.data[k * q + j];
+                }
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Computes the matrix product between the given `n x m` matrix and the `m x k` matrix
+    /// `rhs`, writing the result into `out` and reusing its storage. `out` is cleared first.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::<i32>::from_str("5; 6").unwrap();
+    /// let mut out = Dense::<i32>::zeros(2, 1);
+    ///
+    /// a.matmul_into(&b, &mut out).unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("17; 39").unwrap(), out);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The number of columns of the matrix differs from the number of rows of `rhs`.
+    /// 1. The shape of `out` is not `n x k`.
+    pub fn matmul_into(&self, rhs: &Self, out: &mut Self) -> Result<(), &'static str> {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        if m != p || out.shape() != (n, q) {
+            return Err("Invalid shape for matrix");
+        }
+
+        out.data.fill(T::from(0));
+        for i in 0..n {
+            for k in 0..m {
+                let a = self.data[i * m + k];
+                for j in 0..q {
+                    let acc = &mut out.data[i * q + j];
+                    *acc = *acc + a * rhs.data[k * q + j];
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the matrix-vector product `A·x` between the given `n x m` matrix and `x`,
+    /// returning a vector of len `n`. Returns `None` if the len of `x` differs from `m`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// assert_eq!(Some(vec![5, 11]), mat.mul_vec(&[1, 2]));
+    /// assert_eq!(None, mat.mul_vec(&[1, 2, 3]));
+    /// ```
+    pub fn mul_vec(&self, x: &[T]) -> Option<Vec<T>> {
+        if x.len() != self.m {
+            return None;
+        }
+
+        let res = (0..self.n)
+            .map(|i| {
+                self.data[i * self.m..(i + 1) * self.m]
+                    .iter()
+                    .zip(x)
+                    .fold(T::from(0), |acc, (&a, &b)| acc + a * b)
+            })
+            .collect();
+        Some(res)
+    }
+
+    /// Computes the matrix product between the given matrix and `rhs` tiling the
+    /// computation into products of `block x block` submatrices, which keeps the
+    /// working set in cache for large matrices. The result is identical to the one
+    /// of `matmul`. Returns `None` if `block` is zero or if the number of columns
+    /// of the matrix differs from the number of rows of `rhs`.
+    ///
+    /// Both products can be timed with `cargo bench --bench matmul`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+    /// let b = Dense::<i32>::from_str("1 0; 0 1; 1 1").unwrap();
+    /// assert_eq!(a.matmul(&b), a.matmul_blocked(&b, 2));
+    /// ```
+    pub fn matmul_blocked(&self, rhs: &Self, block: usize) -> Option<Self> {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        if m != p || block == 0 {
+            return None;
+        }
+
+        let mut res = Self::zeros(n, q);
+        for ii in (0..n).step_by(block) {
+            for kk in (0..m).step_by(block) {
+                for jj in (0..q).step_by(block) {
+                    let cols = jj..(jj + block).min(q);
+                    for i in ii..(ii + block).min(n) {
+                        for k in kk..(kk + block).min(m) {
+                            let a = self.data[i * m + k];
+                            let row = &rhs.data[k * q + cols.start..k * q + cols.end];
+                            let out = &mut res.data[i * q + cols.start..i * q + cols.end];
+                            for (acc, &b) in out.iter_mut().zip(row) {
+                                *acc = *acc + a * b;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Computes the matrix product between the given matrix and `rhs` computing the rows
+    /// of the result in parallel. The result is identical to the one of `matmul`.
+    /// Returns `None` if the number of columns of the matrix differs
+    /// from the number of rows of `rhs`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::<i32>::from_str("5; 6").unwrap();
+    /// assert_eq!(a.matmul(&b), a.par_matmul(&b));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_matmul(&self, rhs: &Self) -> Option<Self>
+    where
+        T: Send + Sync,
+    {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        if m != p {
+            return None;
+        }
+
+        let mut res = Self::zeros(n, q);
+        if q == 0 {
+            return Some(res);
+        }
+
+        res.data.par_chunks_mut(q).enumerate().for_each(|(i, row)| {
+            for k in 0..m {
+                let a = self.data[i * m + k];
+                for (acc, &b) in row.iter_mut().zip(&rhs.data[k * q..(k + 1) * q]) {
+                    *acc = *acc + a * b;
+                }
+            }
+        });
+
+        Some(res)
+    }
+
+    /// Raises the matrix to the power `exp` using exponentiation by squaring.
+    /// Returns the identity if `exp` is `0` and `None` if the matrix is not square.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 1; 1 0").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("89 55; 55 34").ok(), mat.pow(10));
+    /// assert_eq!(None, Dense::<i32>::zeros(2, 3).pow(2));
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> Option<Self> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let mut res = Self::identity(self.n);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res.matmul(&base)?;
+            }
+
+            exp >>= 1;
+            if exp > 0 {
+                base = base.matmul(&base)?;
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Formats the matrix with `precision` decimals, see `display::grid` for the layout.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1234.5678 2; -3 4").unwrap();
+    /// assert_eq!("[ 1234.57    2.00 ]\n[   -3.00    4.00 ]", mat.to_string_with(2, 7));
+    /// ```
+    pub fn to_string_with(&self, precision: usize, width: usize) -> String {
+        let (n, m) = self.shape();
+        grid(n, m, width, |i, j| format!("{:.precision$}", self[(i, j)]))
+    }
+
+    /// Returns the vectorization of the matrix by stacking its columns.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// assert_eq!(vec![1.0, 3.0, 2.0, 4.0], mat.vec());
+    /// ```
+    pub fn vec(&self) -> Vec<T> {
+        let (n, m) = self.shape();
+        (0..m)
+            .flat_map(|j| (0..n).map(move |i| (i, j)))
+            .map(|idx| self[idx])
+            .collect()
+    }
+
+    /// Returns a copy of the `i`-th row of the matrix.
+    /// If the given index is out of bounds returns the `None` variant.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// assert_eq!(Some(vec![3.0, 4.0]), mat.row(1));
+    /// assert_eq!(None, mat.row(2));
+    /// ```
+    pub fn row(&self, i: usize) -> Option<Vec<T>> {
+        (i < self.n).then(|| self.data[i * self.m..(i + 1) * self.m].to_vec())
+    }
+
+    /// Returns a copy of the `j`-th column of the matrix.
+    /// If the given index is out of bounds returns the `None` variant.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// assert_eq!(Some(vec![2.0, 4.0]), mat.col(1));
+    /// assert_eq!(None, mat.col(2));
+    /// ```
+    pub fn col(&self, j: usize) -> Option<Vec<T>> {
+        (j < self.m).then(|| self.data.iter().skip(j).step_by(self.m).copied().collect())
+    }
+
+    /// Overwrites the `i`-th row of the matrix with the given `row`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::zeros(2, 2);
+    /// mat.set_row(0, &[1.0, 2.0]).unwrap();
+    /// assert_eq!(Dense::from_str("1 2; 0 0").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The index `i` is out of bounds.
+    /// 1. The len of `row` differs from the number of columns of the matrix.
+    pub fn set_row(&mut self, i: usize, row: &[T]) -> Result<(), &'static str> {
+        if i >= self.n {
+            return Err("Index out of range");
+        }
+
+        if row.len() != self.m {
+            return Err("Invalid quantity of elements");
+        }
+
+        self.data[i * self.m..(i + 1) * self.m].copy_from_slice(row);
+        Ok(())
+    }
+
+    /// Computes the sum of the entries in the diagonal of the matrix.
+    /// Returns `None` if the matrix is not square.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// assert_eq!(Some(5.0), mat.trace());
+    /// ```
+    pub fn trace(&self) -> Option<T> {
+        self.is_square()
+            .then(|| (0..self.n).fold(T::from(0), |acc, i| acc + self[(i, i)]))
+    }
+
+    /// Returns the position of the first entry of the `k`-th diagonal
+    /// alongside its len, which is zero if the diagonal is out of range.
+    fn diagonal_start(&self, k: i64) -> ((usize, usize), usize) {
+        let offset = k.unsigned_abs() as usize;
+        let (i, j) = if k >= 0 { (0, offset) } else { (offset, 0) };
+        let len = self.n.saturating_sub(i).min(self.m.saturating_sub(j));
+        ((i, j), len)
+    }
+
+    /// Returns the entries of the `k`-th diagonal of the matrix, where `k = 0` is the main
+    /// diagonal, positive values of `k` are above it and negative values are below it.
+    /// Returns an empty `Vec` if the diagonal is out of range.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+    /// assert_eq!(vec![2, 6], mat.diagonal_k(1));
+    /// assert_eq!(vec![4], mat.diagonal_k(-1));
+    /// assert!(mat.diagonal_k(3).is_empty());
+    /// ```
+    pub fn diagonal_k(&self, k: i64) -> Vec<T> {
+        let ((i, j), len) = self.diagonal_start(k);
+        (0..len).map(|d| self[(i + d, j + d)]).collect()
+    }
+
+    /// Overwrites the `k`-th diagonal of the matrix with the given `values`, where `k = 0`
+    /// is the main diagonal, positive values of `k` are above it and negative values are
+    /// below it.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::<i32>::zeros(2, 3);
+    /// mat.set_diagonal_k(1, &[1, 2]).unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("0 1 0; 0 0 2").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if the len of `values`
+    /// differs from the len of the `k`-th diagonal.
+    pub fn set_diagonal_k(&mut self, k: i64, values: &[T]) -> Result<&mut Self, &'static str> {
+        let ((i, j), len) = self.diagonal_start(k);
+        if values.len() != len {
+            return Err("Invalid quantity of elements");
+        }
+
+        for (d, &val) in values.iter().enumerate() {
+            self[(i + d, j + d)] = val;
+        }
+
+        Ok(self)
+    }
+
+    /// Concatenates the columns of `rhs` to the right of the given matrix.
+    /// Returns `None` if both matrices don't have the same number of rows.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1; 2").unwrap();
+    /// let b = Dense::<i32>::from_str("3 4; 5 6").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("1 3 4; 2 5 6").ok(), a.hstack(&b));
+    /// ```
+    pub fn hstack(&self, rhs: &Self) -> Option<Self> {
+        if self.n != rhs.n {
+            return None;
+        }
+
+        let (n, m) = (self.n, self.m + rhs.m);
+        let mut data = Vec::with_capacity(n * m);
+        for i in 0..n {
+            data.extend_from_slice(&self.data[i * self.m..(i + 1) * self.m]);
+            data.extend_from_slice(&rhs.data[i * rhs.m..(i + 1) * rhs.m]);
+        }
+
+        Some(Self { data, n, m })
+    }
+
+    /// Concatenates the rows of `rhs` below the given matrix.
+    /// Returns `None` if both matrices don't have the same number of columns.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2").unwrap();
+    /// let b = Dense::<i32>::from_str("3 4; 5 6").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("1 2; 3 4; 5 6").ok(), a.vstack(&b));
+    /// ```
+    pub fn vstack(&self, rhs: &Self) -> Option<Self> {
+        if self.m != rhs.m {
+            return None;
+        }
+
+        let data = [self.data.as_slice(), rhs.data.as_slice()].concat();
+        Some(Self {
+            data,
+            n: self.n + rhs.n,
+            m: self.m,
+        })
+    }
+
+    /// Returns a copy of the rectangular region of the matrix spanning the given
+    /// `rows` and `cols`. Returns `None` if the ranges exceed the shape of the matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("2 3").ok(), mat.submatrix(0..1, 1..3));
+    /// assert_eq!(None, mat.submatrix(0..3, 0..1));
+    /// ```
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Option<Self> {
+        if rows.start > rows.end || rows.end > self.n || cols.start > cols.end || cols.end > self.m
+        {
+            return None;
+        }
+
+        let (n, m) = (rows.len(), cols.len());
+        let mut data = Vec::with_capacity(n * m);
+        for i in rows {
+            data.extend_from_slice(&self.data[i * self.m + cols.start..i * self.m + cols.end]);
+        }
+
+        Some(Self { data, n, m })
+    }
+
+    /// Computes the element-wise (Hadamard) product between the given matrix and `rhs`.
+    /// Returns `None` if both matrices don't have the same shape.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::<i32>::from_str("5 6; 7 8").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("5 12; 21 32").ok(), a.hadamard(&b));
+    /// ```
+    pub fn hadamard(&self, rhs: &Self) -> Option<Self> {
+        if self.shape() != rhs.shape() {
+            return None;
+        }
+
+        let data = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .map(|(&a, &b)| a * b)
+            .collect();
+        Some(Self { data, ..*self })
+    }
+
+    /// Computes the dot product between the given matrix and `rhs`, where both are
+    /// vectors stored either as a single row or as a single column.
+    /// Returns `None` if either matrix is not a vector or their lengths differ.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let row = Dense::<i32>::from_str("1 2 3").unwrap();
+    /// let col = Dense::<i32>::from_str("4; 5; 6").unwrap();
+    /// assert_eq!(Some(32), row.dot(&col));
+    /// ```
+    pub fn dot(&self, rhs: &Self) -> Option<T> {
+        let is_vector = |mat: &Self| mat.n == 1 || mat.m == 1;
+        if !is_vector(self) || !is_vector(rhs) || self.data.len() != rhs.data.len() {
+            return None;
+        }
+
+        let sum = self
+            .data
+            .iter()
+            .zip(&rhs.data)
+            .fold(T::from(0), |acc, (&a, &b)| acc + a * b);
+        Some(sum)
+    }
+
+    /// Computes the Kronecker product between the given `n x m` matrix and the `p x q`
+    /// matrix `rhs`. The result is the `(n * p) x (m * q)` block matrix where block
+    /// `(i, j)` is `rhs` scaled by the entry `(i, j)` of the given matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2").unwrap();
+    /// let b = Dense::<i32>::from_str("1; 10").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("1 2; 10 20").unwrap(), a.kron(&b));
+    /// ```
+    pub fn kron(&self, rhs: &Self) -> Self {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        let cols = m * q;
+
+        let mut data = vec![T::from(0); n * p * cols];
+        for i in 0..n {
+            for j in 0..m {
+                let a = self.data[i * m + j];
+                for k in 0..p {
+                    let start = (i * p + k) * cols + j * q;
+                    let row = &rhs.data[k * q..(k + 1) * q];
+                    for (dst, &b) in data[start..start + q].iter_mut().zip(row) {
+                        *dst = a * b;
+                    }
+                }
+            }
+        }
+
+        Self {
+            data,
+            n: n * p,
+            m: cols,
+        }
+    }
+
+    /// Returns the transpose of the given matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("1 4; 2 5; 3 6").unwrap(), mat.transpose());
+    /// ```
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.m, self.n, |i, j| self[(j, i)])
+    }
+
+    /// Writes the transpose of the given `n x m` matrix into `out`, reusing its storage.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+    /// let mut out = Dense::<i32>::zeros(3, 2);
+    ///
+    /// mat.transpose_into(&mut out).unwrap();
+    /// assert_eq!(mat.transpose(), out);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The shape of `out` is not `m x n`.
+    pub fn transpose_into(&self, out: &mut Self) -> Result<(), &'static str> {
+        let (n, m) = self.shape();
+        if out.shape() != (m, n) {
+            return Err("Invalid shape for matrix");
+        }
+
+        for i in 0..n {
+            for j in 0..m {
+                out.data[j * n + i] = self.data[i * m + j];
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Dense {
+    /// Initializes a new `N x M` matrix filled with zeros.
+    pub fn zeros(n: usize, m: usize) -> Self {
+        DenseConstructors::zeros(n, m)
+    }
+
+    /// Returns the `n x n` identity matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let res = Dense::from_str("1 0; 0 1").unwrap();
+    /// assert_eq!(res, Dense::identity(2));
+    /// ```
+    pub fn identity(n: usize) -> Self {
+        DenseConstructors::identity(n)
+    }
+
+    /// Initializes a new `N x M` matrix where every entry `(i, j)` is set to `f(i, j)`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_fn(2, 3, |i, j| (i + j) as f32);
+    /// let res = Dense::from_str("0 1 2; 1 2 3").unwrap();
+    /// assert_eq!(res, mat);
+    /// ```
+    pub fn from_fn<F: Fn(usize, usize) -> Entry>(n: usize, m: usize, f: F) -> Self {
+        DenseConstructors::from_fn(n, m, f)
+    }
+
+    /// Creates a `Dense` matrix from a string, following the syntax of its `FromStr`
+    /// implementation. Lets `Dense::from_str` build a `Dense<f32>` without annotations.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, &'static str> {
+        FromStr::from_str(s)
+    }
+
+    /// Initializes a new `N x M` matrix filled with random values.
+    pub fn rand(n: usize, m: usize) -> Self {
+        let mut rand_gen = rand::thread_rng();
+        let data = (0..n * m).map(|_| rand_gen.gen()).collect();
+        Self { data, n, m }
+    }
+
+    /// Initializes a new `n x m` matrix filled with standard normal samples drawn from `rand_gen`.
+    fn rand_normal<R: Rng>(n: usize, m: usize, rand_gen: &mut R) -> Self {
+        // Box-Muller transform from two uniform samples in (0, 1].
+        let mut normal = || {
+            let u1: Entry = 1.0 - rand_gen.gen::<Entry>();
+            let u2: Entry = rand_gen.gen();
+            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+        };
+
+        let data = (0..n * m).map(|_| normal()).collect();
+        Self { data, n, m }
+    }
+
+    /// Generates a random `n x n` orthogonal matrix taking the `Q` factor of the QR
+    /// decomposition of a matrix with standard normal entries. The columns of `Q` are
+    /// flipped to make the diagonal of `R` positive, so the result is uniformly
+    /// distributed. The same `seed` always generates the same matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let q = Dense::rand_orthogonal(4, 42);
+    /// assert!(q.is_orthogonal(1e-5));
+    /// assert_eq!(q, Dense::rand_orthogonal(4, 42));
+    /// ```
+    pub fn rand_orthogonal(n: usize, seed: u64) -> Self {
+        let mut rand_gen = StdRng::seed_from_u64(seed);
+        let (mut q, r) = Self::rand_normal(n, n, &mut rand_gen).qr();
+        q.apply_indexed(|(_, j), e| if r[(j, j)] < 0.0 { -e } else { e });
+        q
+    }
+
+    /// Generates a random `n x m` matrix of the given `rank` as the product of an
+    /// `n x rank` and a `rank x m` matrix with standard normal entries, which have full
+    /// rank with probability one. The same `seed` always generates the same matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::rand_rank(5, 4, 2, 7);
+    /// assert_eq!((5, 4), mat.shape());
+    /// assert_eq!(2, mat.rank());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rank` is greater than `MIN(n, m)`.
+    pub fn rand_rank(n: usize, m: usize, rank: usize, seed: u64) -> Self {
+        assert!(
+            rank <= n.min(m),
+            "Rank can't be greater than the smallest dimension"
+        );
+
+        let mut rand_gen = StdRng::seed_from_u64(seed);
+        let left = Self::rand_normal(n, rank, &mut rand_gen);
+        let right = Self::rand_normal(rank, m, &mut rand_gen);
+        &left * &right
+    }
+
+    /// Returns `true` if the matrix is square and `Qᵀ·Q` is the identity,
+    /// comparing every entry within `eps`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// assert!(Dense::from_str("0 1; -1 0").unwrap().is_orthogonal(0.0));
+    /// assert!(!Dense::from_str("1 1; 0 1").unwrap().is_orthogonal(1e-5));
+    /// ```
+    pub fn is_orthogonal(&self, eps: Entry) -> bool {
+        self.is_square()
+            && self
+                .transpose()
+                .matmul(self)
+                .is_some_and(|qtq| qtq.approx_eq(&Dense::identity(self.n), eps))
+    }
+
+    /// Computes an orthonormal basis for the space spanned by the columns of the matrix
+    /// using the modified Gram-Schmidt process. The basis vectors are returned as the
+    /// columns of a new matrix.
+    ///
+    /// Columns that are linearly dependent on the previous ones, relative to their own
+    /// norm, are dropped, so the resulting matrix has as many columns as the rank of
+    /// the given matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("3 6 1; 0 0 0; 4 8 1").unwrap();
+    /// let basis = mat.gram_schmidt();
+    /// assert_eq!((3, 2), basis.shape());
+    /// ```
+    pub fn gram_schmidt(&self) -> Dense {
+        let (n, m) = self.shape();
+        let mut basis: Vec<Vec<Entry>> = Vec::with_capacity(m);
+
+        for j in 0..m {
+            let mut v: Vec<Entry> = (0..n).map(|i| self[(i, j)]).collect();
+            let tol = EPSILON * dot(&v, &v).sqrt();
+
+            for q in &basis {
+                let proj = dot(q, &v);
+                v.iter_mut().zip(q).for_each(|(e, q)| *e -= proj * q);
+            }
+
+            let norm = dot(&v, &v).sqrt();
+            if norm > tol {
+                v.iter_mut().for_each(|e| *e /= norm);
+                basis.push(v);
+            }
+        }
+
+        let k = basis.len();
+        let mut res = Dense::zeros(n, k);
+        for (j, q) in basis.iter().enumerate() {
+            for (i, &e) in q.iter().enumerate() {
+                res[(i, j)] = e;
+            }
+        }
+
+        res
+    }
+
+    /// Computes the vector projection of `u` onto `onto`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let proj = Dense::project(&[3.0, 4.0], &[2.0, 0.0]).unwrap();
+    /// assert_eq!(vec![3.0, 0.0], proj);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. `u` and `onto` have different lengths.
+    /// 1. `onto` is the zero vector.
+    pub fn project(u: &[Entry], onto: &[Entry]) -> Result<Vec<Entry>, &'static str> {
+        if u.len() != onto.len() {
+            return Err("Vectors have different lengths");
+        }
+
+        let norm = dot(onto, onto);
+        if norm == 0.0 {
+            return Err("Cannot project onto the zero vector");
+        }
+
+        let scale = dot(u, onto) / norm;
+        Ok(onto.iter().map(|e| e * scale).collect())
+    }
+
+    /// Computes the angle in radians between the vectors `u` and `v`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let angle = Dense::vector_angle(&[1.0, 0.0], &[1.0, 1.0]).unwrap();
+    /// assert!((angle - std::f64::consts::FRAC_PI_4).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. `u` and `v` have different lengths.
+    /// 1. Either `u` or `v` is the zero vector.
+    pub fn vector_angle(u: &[Entry], v: &[Entry]) -> Result<f64, &'static str> {
+        if u.len() != v.len() {
+            return Err("Vectors have different lengths");
+        }
+
+        let norm_u = (dot(u, u) as f64).sqrt();
+        let norm_v = (dot(v, v) as f64).sqrt();
+        if norm_u == 0.0 || norm_v == 0.0 {
+            return Err("Angle is undefined for the zero vector");
+        }
+
+        let cos = dot(u, v) as f64 / (norm_u * norm_v);
+        Ok(cos.clamp(-1.0, 1.0).acos())
+    }
+
+    /// Computes the cosine similarity between the `i`-th and `j`-th rows of the matrix.
+    /// Returns `None` if either index is out of range or either row is all zeros.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 0; 0 1; 2 0").unwrap();
+    /// assert_eq!(Some(0.0), mat.row_cosine_similarity(0, 1));
+    /// assert_eq!(Some(1.0), mat.row_cosine_similarity(0, 2));
+    /// assert_eq!(None, mat.row_cosine_similarity(0, 3));
+    /// ```
+    pub fn row_cosine_similarity(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= self.n || j >= self.n {
+            return None;
+        }
+
+        let u = &self.data[i * self.m..(i + 1) * self.m];
+        let v = &self.data[j * self.m..(j + 1) * self.m];
+
+        let norm_u = (dot(u, u) as f64).sqrt();
+        let norm_v = (dot(v, v) as f64).sqrt();
+        if norm_u == 0.0 || norm_v == 0.0 {
+            return None;
+        }
+
+        Some(dot(u, v) as f64 / (norm_u * norm_v))
+    }
+
+    /// Treats every row of the matrix as a point and returns the `n x n` matrix
+    /// of Euclidean distances between every pair of points.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let points = Dense::from_str("0 0; 3 4").unwrap();
+    /// let res = Dense::from_str("0 5; 5 0").unwrap();
+    /// assert_eq!(res, points.pairwise_distances());
+    /// ```
+    pub fn pairwise_distances(&self) -> Dense {
+        let (n, m) = self.shape();
+        let mut res = Dense::zeros(n, n);
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let dist = (0..m)
+                    .map(|k| (self[(i, k)] - self[(j, k)]).powi(2))
+                    .sum::<Entry>()
+                    .sqrt();
+
+                res[(i, j)] = dist;
+                res[(j, i)] = dist;
+            }
+        }
+
+        res
+    }
+
+    /// Scales the matrix by `det^(-1/n)` so that the determinant of the result is `1`.
+    /// Returns `None` if the matrix is not square or its determinant is not positive.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("2 0; 0 8").unwrap();
+    /// let res = Dense::from_str("0.5 0; 0 2").unwrap();
+    /// assert_eq!(Some(res), mat.to_unit_determinant());
+    /// ```
+    pub fn to_unit_determinant(&self) -> Option<Dense> {
+        let det = self.det()?;
+        if det <= 0.0 {
+            return None;
+        }
+
+        let mut res = self.clone();
+
+        res.scalar_mul(det.powf(-1.0 / self.n as Entry));
+        Some(res)
+    }
+
+    /// Computes `tr(A·B)` without computing the product between `a` and `b`.
+    /// Returns `None` if the product `A·B` is not a square matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::from_str("5 6; 7 8").unwrap();
+    /// assert_eq!(Some(69.0), Dense::trace_of_product(&a, &b));
+    /// ```
+    pub fn trace_of_product(a: &Dense, b: &Dense) -> Option<Entry> {
+        let (n, m) = a.shape();
+        if (m, n) != b.shape() {
+            return None;
+        }
+
+        let trace = (0..n)
+            .flat_map(|i| (0..m).map(move |k| (i, k)))
+            .map(|(i, k)| a[(i, k)] * b[(k, i)])
+            .sum();
+
+        Some(trace)
+    }
+
+    /// Computes `tr(A^k)` by repeatedly multiplying the matrix by itself.
+    /// Returns `None` if the matrix is not square.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 1; 0 2").unwrap();
+    /// assert_eq!(Some(2.0), mat.trace_power(0));
+    /// assert_eq!(Some(9.0), mat.trace_power(3));
+    /// ```
+    pub fn trace_power(&self, k: u32) -> Option<Entry> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let n = self.n;
+        if k == 0 {
+            return Some(n as Entry);
+        }
+
+        let mut pow = self.clone();
+
+        for _ in 1..k {
+            pow = pow.matmul(self)?;
+        }
+
+        pow.trace()
+    }
+
+    /// Converts the matrix into a `Sparse` matrix, storing only the entries whose
+    /// absolute value is greater than `eps`.
+    /// Returns `None` if the shape of the matrix differs from `(N, M)`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// use mat_lib::sparse::Sparse;
+    /// let mat = Dense::from_str("1 0; 0.00001 2").unwrap();
+    /// let sparse = mat.to_sparse::<2, 2>(1e-3).unwrap();
+    /// assert_eq!(2, sparse.nnz());
+    ///
+    /// assert!(mat.to_sparse::<3, 2>(1e-3).is_none());
+    /// ```
+    pub fn to_sparse<const N: usize, const M: usize>(
+        &self,
+        eps: Entry,
+    ) -> Option<Sparse<Entry, N, M>> {
+        if self.shape() != (N, M) {
+            return None;
+        }
+
+        let mut res = Sparse::zeros();
+        for (k, &e) in self.data.iter().enumerate() {
+            if e.abs() > eps {
+                res.set((k / M, k % M), e);
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Creates a `Dense` matrix from the contents of a NumPy `.npy` file.
+    ///
+    /// Only 2-dimensional little-endian `f32` (`<f4`) arrays in C order are supported.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (1, 2), }\n";
+    /// let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    /// bytes.extend((header.len() as u16).to_le_bytes());
+    /// bytes.extend(header.as_bytes());
+    /// bytes.extend([1.0f32, 2.0].iter().flat_map(|e| e.to_le_bytes()));
+    ///
+    /// assert_eq!(Dense::from_str("1 2").unwrap(), Dense::from_npy(&bytes).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The buffer is not a valid `.npy` file.
+    /// 1. The stored array has an unsupported dtype, order or shape.
+    pub fn from_npy(bytes: &[u8]) -> Result<Dense, &'static str> {
+        let (data, n, m) = super::npy::mat_from_npy(bytes)?;
+        Ok(Self { data, n, m })
+    }
+
+    /// Writes the matrix as the contents of a NumPy `.npy` file holding
+    /// a little-endian `f32` (`<f4`) array in C order.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2 3; 4 5 6").unwrap();
+    /// let bytes = mat.to_npy();
+    /// assert_eq!(mat, Dense::from_npy(&bytes).unwrap());
+    /// ```
+    pub fn to_npy(&self) -> Vec<u8> {
+        super::npy::mat_to_npy(&self.data, self.n, self.m)
+    }
+
+    /// Computes the Kronecker sum `A ⊗ I + I ⊗ B` between the given matrix `A` and `rhs: B`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::from_str("10").unwrap();
+    ///
+    /// let res = Dense::from_str("11 2; 3 14").unwrap();
+    /// assert_eq!(Ok(res), a.kron_sum(&b));
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if either matrix is not square.
+    pub fn kron_sum(&self, rhs: &Dense) -> Result<Dense, &'static str> {
+        if !self.is_square() || !rhs.is_square() {
+            return Err("Kronecker sum is only defined for square matrices");
+        }
+
+        let (n, m) = (self.n, rhs.n);
+        let mut res = Dense::zeros(n * m, n * m);
+
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..m {
+                    res[(i * m + k, j * m + k)] += self[(i, j)];
+                }
+            }
+        }
+
+        for i in 0..n {
+            for k in 0..m {
+                for l in 0..m {
+                    res[(i * m + k, i * m + l)] += rhs[(k, l)];
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Builds an `n x m` matrix from the stacked columns in `data`.
+    /// This is the inverse operation of `vec`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::unvec(&[1.0, 3.0, 2.0, 4.0], 2, 2).unwrap();
+    /// assert_eq!(Dense::from_str("1 2; 3 4").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if the len of `data` differs from `n * m`.
+    pub fn unvec(data: &[Entry], n: usize, m: usize) -> Result<Dense, &'static str> {
+        if data.len() != n * m {
+            return Err("Invalid quantity of elements");
+        }
+
+        let mut res = Dense::zeros(n, m);
+        for (k, &e) in data.iter().enumerate() {
+            res[(k % n, k / n)] = e;
+        }
+
+        Ok(res)
+    }
+
+    /// Computes the commutator `AB - BA` between the given matrix `A` and `b: B`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("0 1; 0 0").unwrap();
+    /// let b = Dense::from_str("0 0; 1 0").unwrap();
+    ///
+    /// let res = Dense::from_str("1 0; 0 -1").unwrap();
+    /// assert_eq!(Ok(res), a.commutator(&b));
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if the matrices are not
+    /// square or don't have the same shape.
+    pub fn commutator(&self, b: &Dense) -> Result<Dense, &'static str> {
+        if !self.is_square() || self.shape() != b.shape() {
+            return Err("Commutator is only defined for square matrices of the same shape");
+        }
+
+        let ab = self.matmul(b).ok_or("Invalid shape for matrix")?;
+        let ba = b.matmul(self).ok_or("Invalid shape for matrix")?;
+        ab.sub(&ba).ok_or("Invalid shape for matrix")
+    }
+
+    /// Returns `true` if every entry of the commutator between the given matrix
+    /// and `b` is within `eps` of zero. Matrices without a commutator never commute.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2; 2 1").unwrap();
+    /// let b = Dense::from_str("3 1; 1 3").unwrap();
+    /// assert!(a.commutes_with(&b, 1e-6));
+    /// ```
+    pub fn commutes_with(&self, b: &Dense, eps: Entry) -> bool {
+        self.commutator(b)
+            .map(|c| c.data.iter().all(|e| e.abs() <= eps))
+            .unwrap_or(false)
+    }
+
+    /// Computes the matrix exponential `e^A` using scaling and squaring: the matrix is
+    /// scaled down until its norm is below `1`, the exponential is approximated with
+    /// the first `terms` terms of its Taylor series and the result is squared back.
+    /// Returns `None` if the matrix is not square or its norm is not finite.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::zeros(2, 2);
+    /// let res = Dense::from_str("1 0; 0 1").unwrap();
+    /// assert_eq!(Some(res), mat.expm(10));
+    /// ```
+    pub fn expm(&self, terms: usize) -> Option<Dense> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let n = self.n;
+        let norm = (0..n)
+            .map(|i| {
+                self.data[i * n..(i + 1) * n]
+                    .iter()
+                    .map(|e| e.abs())
+                    .sum::<Entry>()
+            })
+            .fold(0.0, Entry::max);
+
+        if !norm.is_finite() {
+            return None;
+        }
+
+        let squarings = if norm > 0.5 {
+            norm.log2().ceil() as i32 + 1
+        } else {
+            0
+        };
+        let mut scaled = self.clone();
+        scaled.scalar_mul((2.0 as Entry).powi(-squarings));
+
+        let mut res = Dense::identity(n);
+        let mut term = Dense::identity(n);
+
+        for k in 1..terms {
+            term = term.matmul(&scaled)?;
+            term.scalar_mul(1.0 / k as Entry);
+            res.data
+                .iter_mut()
+                .zip(&term.data)
+                .for_each(|(e, t)| *e += t);
+        }
+
+        for _ in 0..squarings {
+            res = res.matmul(&res)?;
+        }
+
+        Some(res)
+    }
+
+    /// Builds the `n x n` Givens rotation matrix that rotates the `(i, j)` plane
+    /// by `theta` radians, leaving every other coordinate untouched.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let rot = Dense::givens(2, 0, 1, std::f32::consts::FRAC_PI_2).unwrap();
+    /// let x = Dense::from_str("1; 0").unwrap();
+    /// let y = rot.matmul(&x).unwrap();
+    /// assert!((y[(1, 0)] - 1.0).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if `i` or `j` are
+    /// out of bounds or if they are equal.
+    pub fn givens(n: usize, i: usize, j: usize, theta: Entry) -> Result<Dense, &'static str> {
+        if i >= n || j >= n {
+            return Err("Index out of range");
+        }
+
+        if i == j {
+            return Err("Rotation plane needs two different indices");
+        }
+
+        let (sin, cos) = theta.sin_cos();
+        let mut res = Dense::identity(n);
+        res[(i, i)] = cos;
+        res[(j, j)] = cos;
+        res[(i, j)] = -sin;
+        res[(j, i)] = sin;
+        Ok(res)
+    }
+
+    /// Left multiplies the matrix in-place by the Householder reflection
+    /// `I - 2 v vᵀ / (vᵀv)` that reflects across the hyperplane orthogonal to `v`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("3; 4").unwrap();
+    ///
+    /// // Reflects the column onto the first axis.
+    /// mat.apply_householder(&[3.0 - 5.0, 4.0]).unwrap();
+    /// assert_eq!(Dense::from_str("5; 0").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The len of `v` differs from the number of rows of the matrix.
+    /// 1. `v` is the zero vector.
+    pub fn apply_householder(&mut self, v: &[Entry]) -> Result<&mut Self, &'static str> {
+        let (n, m) = self.shape();
+        if v.len() != n {
+            return Err("Invalid quantity of elements");
+        }
+
+        let norm = dot(v, v);
+        if norm == 0.0 {
+            return Err("Cannot reflect across the zero vector");
+        }
+
+        for j in 0..m {
+            let proj = (0..n).map(|i| v[i] * self[(i, j)]).sum::<Entry>() * 2.0 / norm;
+            for (i, &vi) in v.iter().enumerate() {
+                self[(i, j)] -= proj * vi;
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the lower and upper bandwidths of the matrix, only taking into
+    /// account the entries whose absolute value is greater than `eps`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2 0; 0 1 0; 3 0 1").unwrap();
+    /// assert_eq!((2, 1), mat.bandwidth(0.0));
+    /// ```
+    pub fn bandwidth(&self, eps: Entry) -> (usize, usize) {
+        let (mut lower, mut upper) = (0, 0);
+
+        for (k, e) in self.data.iter().enumerate() {
+            if e.abs() <= eps {
+                continue;
+            }
+
+            let (i, j) = (k / self.m, k % self.m);
+            if i > j {
+                lower = lower.max(i - j);
+            } else {
+                upper = upper.max(j - i);
+            }
+        }
+
+        (lower, upper)
+    }
+
+    /// Sets to zero every entry outside the band made of the `kl` diagonals
+    /// below the main diagonal and the `ku` diagonals above it.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+    /// mat.truncate_to_band(1, 0);
+    /// assert_eq!(Dense::from_str("1 0 0; 4 5 0; 0 8 9").unwrap(), mat);
+    /// ```
+    pub fn truncate_to_band(&mut self, kl: usize, ku: usize) -> &mut Self {
+        self.apply_indexed(|(i, j), e| {
+            if (i > j && i - j > kl) || (j > i && j - i > ku) {
+                0.0
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Computes the weighted sum `w_1 * M_1 + ... + w_k * M_k` of the given `(M_k, w_k)` pairs.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2").unwrap();
+    /// let b = Dense::from_str("3 4").unwrap();
+    ///
+    /// let res = Dense::from_str("7 10").unwrap();
+    /// assert_eq!(Ok(res), Dense::weighted_sum(&[(&a, 1.0), (&b, 2.0)]));
+    /// ```
+    ///
+    /// # Errors
+    /// 1. `mats` is empty.
+    /// 1. The matrices don't have the same shape.
+    pub fn weighted_sum(mats: &[(&Dense, Entry)]) -> Result<Dense, &'static str> {
+        let (first, _) = mats.first().ok_or("No matrices were given")?;
+        let (n, m) = first.shape();
+
+        let mut res = Dense::zeros(n, m);
+        for (mat, w) in mats {
+            if mat.shape() != (n, m) {
+                return Err("Invalid shape for matrix");
+            }
+
+            res.data
+                .iter_mut()
+                .zip(&mat.data)
+                .for_each(|(e, x)| *e += w * x);
+        }
+
+        Ok(res)
+    }
+
+    /// Returns `true` if both matrices have the same shape and every pair
+    /// of corresponding entries differs by at most `eps`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2").unwrap();
+    /// let b = Dense::from_str("1.0001 2").unwrap();
+    /// assert!(a.approx_eq(&b, 1e-3));
+    /// assert!(!a.approx_eq(&b, 1e-5));
+    /// ```
+    pub fn approx_eq(&self, other: &Dense, eps: Entry) -> bool {
+        self.shape() == other.shape()
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|(a, b)| (a - b).abs() <= eps)
+    }
+
+    /// Smooths the matrix treating every row as a timestep. Each row of the result is
+    /// the average of the last `window` rows up to and including the same row.
+    /// The first `window - 1` rows average only the rows available so far.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1; 3; 5; 7").unwrap();
+    /// let res = Dense::from_str("1; 2; 4; 6").unwrap();
+    /// assert_eq!(Ok(res), mat.moving_average_rows(2));
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if `window` is `0`
+    /// or greater than the number of rows of the matrix.
+    pub fn moving_average_rows(&self, window: usize) -> Result<Dense, &'static str> {
+        let (n, m) = self.shape();
+        if window == 0 || window > n {
+            return Err("Invalid window size");
+        }
+
+        let mut res = Dense::zeros(n, m);
+        let mut sum = vec![0.0; m];
+
+        for i in 0..n {
+            sum.iter_mut()
+                .zip(&self.data[i * m..(i + 1) * m])
+                .for_each(|(s, e)| *s += e);
+            if i >= window {
+                let old = &self.data[(i - window) * m..(i - window + 1) * m];
+                sum.iter_mut().zip(old).for_each(|(s, e)| *s -= e);
+            }
+
+            let len = (i + 1).min(window) as Entry;
+            for (j, s) in sum.iter().enumerate() {
+                res[(i, j)] = s / len;
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Builds an `indices.len() x num_classes` matrix where every row `i`
+    /// has a `1` in the column `indices[i]` and zeros everywhere else.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let res = Dense::from_str("0 1 0; 1 0 0").unwrap();
+    /// assert_eq!(Ok(res), Dense::one_hot(&[1, 0], 3));
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if any index is not less than `num_classes`.
+    pub fn one_hot(indices: &[usize], num_classes: usize) -> Result<Dense, &'static str> {
+        if indices.iter().any(|&k| k >= num_classes) {
+            return Err("Index out of range");
+        }
+
+        Ok(Dense::from_fn(indices.len(), num_classes, |i, j| {
+            if indices[i] == j {
+                1.0
+            } else {
+                0.0
+            }
+        }))
+    }
+
+    /// Returns, for every row, the column index of its greatest entry.
+    /// On ties the first index is returned.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("0.1 0.7 0.2; 0.5 0.3 0.5").unwrap();
+    /// assert_eq!(vec![1, 0], mat.argmax_rows());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix has no columns.
+    pub fn argmax_rows(&self) -> Vec<usize> {
+        assert!(self.m > 0, "Matrix must have at least one column");
+        (0..self.n)
+            .map(|i| {
+                let row = &self.data[i * self.m..(i + 1) * self.m];
+                (1..row.len()).fold(0, |max, j| if row[j] > row[max] { j } else { max })
+            })
+            .collect()
+    }
+
+    /// Computes the LU decomposition with partial pivoting of the given matrix.
+    /// Returns the packed factors, where `U` is stored in the upper triangle and
+    /// the multipliers of `L` below the diagonal, alongside the row permutation.
+    /// Returns `None` if the matrix is not square or is singular.
+    fn lu_factor(&self) -> Option<(Vec<Entry>, Vec<usize>)> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let n = self.n;
+        let mut a = self.data.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let tol = EPSILON * a.iter().fold(0.0, |max: Entry, e| max.max(e.abs()));
+
+        for k in 0..n {
+            let pivot =
+                (k..n).max_by(|&x, &y| a[x * n + k].abs().total_cmp(&a[y * n + k].abs()))?;
+            if a[pivot * n + k].abs() <= tol {
+                return None;
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    a.swap(k * n + j, pivot * n + j);
+                }
+                perm.swap(k, pivot);
+            }
+
+            for i in k + 1..n {
+                let f = a[i * n + k] / a[k * n + k];
+                a[i * n + k] = f;
+                for j in k + 1..n {
+                    a[i * n + j] -= f * a[k * n + j];
+                }
+            }
+        }
+
+        Some((a, perm))
+    }
+
+    /// Computes the LU decomposition with partial pivoting of the given matrix, returning
+    /// the unit lower triangular `L`, the upper triangular `U` and the row permutation
+    /// `perm` such that row `i` of `P·A` is row `perm[i]` of `A` and `P·A = L·U`.
+    /// Returns `None` if the matrix is not square or is singular.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2; 4 4").unwrap();
+    /// let (l, u, perm) = mat.lu().unwrap();
+    /// assert_eq!(vec![1, 0], perm);
+    /// assert_eq!(Dense::from_str("1 0; 0.25 1").unwrap(), l);
+    /// assert_eq!(Dense::from_str("4 4; 0 1").unwrap(), u);
+    /// ```
+    pub fn lu(&self) -> Option<(Dense, Dense, Vec<usize>)> {
+        let n = self.n;
+        let (lu, perm) = self.lu_factor()?;
+
+        let l = Dense::from_fn(n, n, |i, j| match i.cmp(&j) {
+            Ordering::Greater => lu[i * n + j],
+            Ordering::Equal => 1.0,
+            Ordering::Less => 0.0,
+        });
+        let u = Dense::from_fn(n, n, |i, j| if i <= j { lu[i * n + j] } else { 0.0 });
+        Some((l, u, perm))
+    }
+
+    /// Solves the linear system `A·x = b` using the LU decomposition with partial
+    /// pivoting of the given matrix `A` followed by forward and back substitution.
+    /// Returns `None` if the matrix is not square, is singular or if the len of `b`
+    /// differs from the number of rows of the matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("2 1; 1 3").unwrap();
+    /// assert_eq!(Some(vec![1.0, 2.0]), mat.solve(&[4.0, 7.0]));
+    /// ```
+    pub fn solve(&self, b: &[Entry]) -> Option<Vec<Entry>> {
+        if b.len() != self.n {
+            return None;
+        }
+
+        let n = self.n;
+        let (lu, perm) = self.lu_factor()?;
+        let mut x: Vec<Entry> = perm.iter().map(|&p| b[p]).collect();
+
+        for i in 0..n {
+            x[i] -= dot(&lu[i * n..i * n + i], &x[..i]);
+        }
+
+        for i in (0..n).rev() {
+            x[i] -= dot(&lu[i * n + i + 1..(i + 1) * n], &x[i + 1..]);
+            x[i] /= lu[i * n + i];
+        }
+
+        Some(x)
+    }
+
+    /// Computes the `m x m` sample covariance matrix treating every row
+    /// as a sample and every column as a variable.
+    ///
+    /// The sample covariance divides by `n - 1`, so it is undefined
+    /// for less than two samples and a zero matrix is returned instead.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let samples = Dense::from_str("1 2; 3 6").unwrap();
+    /// let res = Dense::from_str("2 4; 4 8").unwrap();
+    /// assert_eq!(res, samples.covariance());
+    /// ```
+    pub fn covariance(&self) -> Dense {
+        let (n, m) = self.shape();
+        let mut res = Dense::zeros(m, m);
+        if n < 2 {
+            return res;
+        }
+
+        let means: Vec<Entry> = (0..m)
+            .map(|j| (0..n).map(|i| self[(i, j)]).sum::<Entry>() / n as Entry)
+            .collect();
+
+        for i in 0..n {
+            for a in 0..m {
+                let da = self[(i, a)] - means[a];
+                for b in a..m {
+                    res[(a, b)] += da * (self[(i, b)] - means[b]);
+                }
+            }
+        }
+
+        for a in 0..m {
+            for b in a..m {
+                res[(a, b)] /= (n - 1) as Entry;
+                res[(b, a)] = res[(a, b)];
+            }
+        }
+
+        res
+    }
+
+    /// Subtracts from every entry the mean of its column, so that every column has zero mean.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("1 4; 3 8").unwrap();
+    /// mat.center_columns();
+    /// assert_eq!(Dense::from_str("-1 -2; 1 2").unwrap(), mat);
+    /// ```
+    pub fn center_columns(&mut self) -> &mut Self {
+        let (n, m) = self.shape();
+        if n == 0 {
+            return self;
+        }
+
+        let means: Vec<Entry> = (0..m)
+            .map(|j| (0..n).map(|i| self[(i, j)]).sum::<Entry>() / n as Entry)
+            .collect();
+
+        self.apply_indexed(|(_, j), e| e - means[j])
+    }
+
+    /// Multiplies every entry in the `i`-th row of the matrix by `factor`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// mat.scale_row(1, 2.0).unwrap();
+    /// assert_eq!(Dense::from_str("1 2; 6 8").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The index `i` is out of bounds.
+    pub fn scale_row(&mut self, i: usize, factor: Entry) -> Result<(), &'static str> {
+        if i >= self.n {
+            return Err("Index out of range");
+        }
+
+        let m = self.m;
+        self.data[i * m..(i + 1) * m]
+            .iter_mut()
+            .for_each(|e| *e *= factor);
+        Ok(())
+    }
+
+    /// Multiplies every entry in the `j`-th column of the matrix by `factor`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// mat.scale_col(0, -1.0).unwrap();
+    /// assert_eq!(Dense::from_str("-1 2; -3 4").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The index `j` is out of bounds.
+    pub fn scale_col(&mut self, j: usize, factor: Entry) -> Result<(), &'static str> {
+        if j >= self.m {
+            return Err("Index out of range");
+        }
+
+        let m = self.m;
+        self.data[j..]
+            .iter_mut()
+            .step_by(m)
+            .for_each(|e| *e *= factor);
+        Ok(())
+    }
+
+    /// Projects the data onto its top `k` principal components, treating every row
+    /// as a sample and every column as a feature. The components are the leading
+    /// eigenvectors of the covariance matrix, found by power iteration with deflation
+    /// running `iters` iterations for each of them.
+    /// Returns `None` if `k` is greater than the number of features.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let data = Dense::from_str("1 1; 2 2; 3 3").unwrap();
+    /// let proj = data.pca(1, 50).unwrap();
+    /// assert_eq!((3, 1), proj.shape());
+    /// ```
+    pub fn pca(&self, k: usize, iters: usize) -> Option<Dense> {
+        let m = self.m;
+        if k > m {
+            return None;
+        }
+
+        let mut cov = self.covariance();
+        let mut components = Dense::zeros(m, k);
+
+        for c in 0..k {
+            // A constant starting vector is orthogonal to the leading
+            // eigenvector of many simple inputs, so a decreasing one is used.
+            let mut v: Vec<Entry> = (0..m).map(|i| 1.0 / (i + 1) as Entry).collect();
+
+            for _ in 0..iters {
+                let w: Vec<Entry> = (0..m)
+                    .map(|i| dot(&cov.data[i * m..(i + 1) * m], &v))
+                    .collect();
+                let norm = dot(&w, &w).sqrt();
+                if norm <= EPSILON {
+                    break;
+                }
+
+                v = w.iter().map(|e| e / norm).collect();
+            }
+
+            let lambda: Entry = (0..m)
+                .map(|i| v[i] * dot(&cov.data[i * m..(i + 1) * m], &v))
+                .sum();
+            cov.apply_indexed(|(i, j), e| e - lambda * v[i] * v[j]);
+
+            for (i, &e) in v.iter().enumerate() {
+                components[(i, c)] = e;
+            }
+        }
+
+        let mut centered = self.clone();
+        centered.center_columns();
+
+        centered.matmul(&components)
+    }
+
+    /// Computes the Frobenius inner product `sum(a_ij * b_ij)` between the given matrix and `b`.
+    /// Returns `None` if both matrices don't have the same shape.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::from_str("1 0; 0 1").unwrap();
+    /// assert_eq!(Some(5.0), a.frobenius_inner(&b));
+    /// ```
+    pub fn frobenius_inner(&self, b: &Dense) -> Option<Entry> {
+        (self.shape() == b.shape()).then(|| dot(&self.data, &b.data))
+    }
+
+    /// Computes the weighted Frobenius norm `sqrt(sum(w_ij * a_ij^2))` of the given matrix.
+    /// Returns `None` if `weights` doesn't have the same shape as the matrix or if any
+    /// of the weights is negative.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("3 1; 0 2").unwrap();
+    /// let w = Dense::from_str("1 0; 5 4").unwrap();
+    /// assert_eq!(Some(5.0), a.weighted_frobenius(&w));
+    /// ```
+    pub fn weighted_frobenius(&self, weights: &Dense) -> Option<f64> {
+        if self.shape() != weights.shape() || weights.data.iter().any(|&w| w < 0.0) {
+            return None;
+        }
+
+        let sum: f64 = self
+            .data
+            .iter()
+            .zip(&weights.data)
+            .map(|(&a, &w)| w as f64 * (a as f64).powi(2))
+            .sum();
+
+        Some(sum.sqrt())
+    }
+
+    /// Writes the matrix as comma separated values, one row per line.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2.5; -3 4").unwrap();
+    /// assert_eq!("1,2.5\n-3,4\n", mat.to_csv());
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut s = String::new();
+        for i in 0..self.n {
+            let row: Vec<String> = self.data[i * self.m..(i + 1) * self.m]
+                .iter()
+                .map(|e| e.to_string())
+                .collect();
+
+            s.push_str(&row.join(","));
+            s.push('\n');
+        }
+
+        s
+    }
+
+    /// Creates a `Dense` matrix from a string of comma separated values, one row per line.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_csv("1,2.5\n-3,4\n").unwrap();
+    /// assert_eq!(Dense::from_str("1 2.5; -3 4").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The string is empty.
+    /// 1. At least one of the given entries is not a valid `Entry`.
+    /// 1. The rows don't have the same number of entries.
+    pub fn from_csv(s: &str) -> Result<Dense, &'static str> {
+        let (data, n, m) = super::parse::mat_from_csv(s)?;
+        Ok(Self { data, n, m })
+    }
+
+    /// Solves the Sylvester equation `A·X + X·B = C` for `X`, where `A` is `n x n`,
+    /// `B` is `m x m` and `C` is `n x m`. The equation is vectorized into the linear
+    /// system `(I ⊗ A + Bᵀ ⊗ I)·vec(X) = vec(C)`.
+    /// Returns `None` if the shapes don't match or the system is singular.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("1 0; 0 2").unwrap();
+    /// let b = Dense::from_str("3").unwrap();
+    /// let c = Dense::from_str("4; 10").unwrap();
+    ///
+    /// let x = Dense::solve_sylvester(&a, &b, &c).unwrap();
+    /// assert!(x.approx_eq(&Dense::from_str("1; 2").unwrap(), 1e-6));
+    /// ```
+    pub fn solve_sylvester(a: &Dense, b: &Dense, c: &Dense) -> Option<Dense> {
+        let (n, m) = c.shape();
+        if a.shape() != (n, n) || b.shape() != (m, m) {
+            return None;
+        }
+
+        let system = b.transpose().kron_sum(a).ok()?;
+        let x = system.solve(&c.vec())?;
+        Dense::unvec(&x, n, m).ok()
+    }
+
+    /// Solves the discrete Lyapunov equation `A·X·Aᵀ - X + Q = 0` for `X`, where `A` and
+    /// `Q` are `n x n`. The equation is vectorized into the linear system
+    /// `(I - A ⊗ A)·vec(X) = vec(Q)`.
+    ///
+    /// `A` is considered stable when some power `A^(2^k)` with `k < 32` has a Frobenius
+    /// norm below `1`, which guarantees its spectral radius is below `1`.
+    /// Returns `None` if the shapes don't match, `A` is not stable or the system is singular.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("0.5 0; 0 0.5").unwrap();
+    /// let q = Dense::from_str("3 0; 0 3").unwrap();
+    ///
+    /// let x = Dense::solve_lyapunov(&a, &q).unwrap();
+    /// assert!(x.approx_eq(&Dense::from_str("4 0; 0 4").unwrap(), 1e-5));
+    /// ```
+    pub fn solve_lyapunov(a: &Dense, q: &Dense) -> Option<Dense> {
+        let n = a.n;
+        if a.shape() != (n, n) || q.shape() != (n, n) {
+            return None;
+        }
+
+        let mut power = a.clone();
+        let mut stable = false;
+        for _ in 0..32 {
+            let norm = power.frobenius_inner(&power)?.sqrt();
+            if norm < 1.0 {
+                stable = true;
+                break;
+            }
+
+            if !norm.is_finite() {
+                break;
+            }
+
+            power = power.matmul(&power)?;
+        }
+
+        if !stable {
+            return None;
+        }
+
+        let nn = n * n;
+        let system = Dense::from_fn(nn, nn, |r, c| {
+            let (i, k) = (r % n, r / n);
+            let (j, l) = (c % n, c / n);
+            let id = if r == c { 1.0 } else { 0.0 };
+            id - a[(k, l)] * a[(i, j)]
+        });
+
+        let x = system.solve(&q.vec())?;
+        Dense::unvec(&x, n, n).ok()
+    }
+
+    /// Returns `true` if the matrix is symmetric positive-definite. A Cholesky factorization
+    /// is attempted keeping only the packed lower triangle of the factor, stopping at the
+    /// first pivot that is not greater than `eps`.
+    /// Returns `false` if the matrix is not square or not symmetric within `eps`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let spd = Dense::from_str("4 2; 2 3").unwrap();
+    /// assert!(spd.is_positive_definite(1e-9));
+    ///
+    /// let indefinite = Dense::from_str("1 2; 2 1").unwrap();
+    /// assert!(!indefinite.is_positive_definite(1e-9));
+    /// ```
+    pub fn is_positive_definite(&self, eps: f64) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+
+        let n = self.n;
+        let at = |i: usize, j: usize| self[(i, j)] as f64;
+        let symmetric = (0..n).all(|i| (0..i).all(|j| (at(i, j) - at(j, i)).abs() <= eps));
+        if !symmetric {
+            return false;
+        }
+
+        // Row `i` of the factor starts at `i * (i + 1) / 2`.
+        let mut l = vec![0.0; n * (n + 1) / 2];
+        for i in 0..n {
+            let row = i * (i + 1) / 2;
+            for j in 0..=i {
+                let col = j * (j + 1) / 2;
+                let sum: f64 = (0..j).map(|k| l[row + k] * l[col + k]).sum();
+                let val = at(i, j) - sum;
+
+                if i == j {
+                    if val <= eps {
+                        return false;
+                    }
+                    l[row + j] = val.sqrt();
+                } else {
+                    l[row + j] = val / l[col + j];
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if every entry outside the main diagonal is within `eps` of zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 0.001; 0 2").unwrap();
+    /// assert!(mat.is_diagonal(1e-2));
+    /// assert!(!mat.is_diagonal(0.0));
+    /// ```
+    pub fn is_diagonal(&self, eps: Entry) -> bool {
+        self.data
+            .iter()
+            .enumerate()
+            .all(|(k, e)| k / self.m == k % self.m || e.abs() <= eps)
+    }
+
+    /// Symmetrically scales the matrix computing `D⁻¹·A·D⁻¹` where `D = diag(sqrt(|a_ii|))`,
+    /// returning the scaled matrix alongside the diagonal of `D` so the scaling can be undone.
+    /// Returns `None` if the matrix is not square or any entry of its diagonal is zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("4 2; 2 9").unwrap();
+    /// let (scaled, d) = mat.jacobi_scale().unwrap();
+    /// assert_eq!(vec![2.0, 3.0], d);
+    /// assert!(scaled.approx_eq(&Dense::from_str("1 0.33333; 0.33333 1").unwrap(), 1e-5));
+    /// ```
+    pub fn jacobi_scale(&self) -> Option<(Dense, Vec<Entry>)> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let d: Vec<Entry> = (0..self.n).map(|i| self[(i, i)].abs().sqrt()).collect();
+        if d.contains(&0.0) {
+            return None;
+        }
+
+        let mut scaled = self.clone();
+        scaled.apply_indexed(|(i, j), e| e / (d[i] * d[j]));
+        Some((scaled, d))
+    }
+
+    /// Estimates the numerical radius `max |xᴴ·A·x|` over complex unit vectors `x` by
+    /// evaluating `samples` random vectors. The estimate never exceeds the true radius
+    /// and approaches it as `samples` grows. The same `seed` always gives the same estimate.
+    /// Returns `0.0` if the matrix is not square or `samples` is `0`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("2 0; 0 -1").unwrap();
+    /// let r = mat.numerical_radius_estimate(2000, 42);
+    /// assert!(r <= 2.0 + 1e-6 && r > 1.9);
+    /// assert_eq!(r, mat.numerical_radius_estimate(2000, 42));
+    /// ```
+    pub fn numerical_radius_estimate(&self, samples: usize, seed: u64) -> f64 {
+        if !self.is_square() {
+            return 0.0;
+        }
+
+        let n = self.n;
+        let at = |i: usize, j: usize| self[(i, j)] as f64;
+        let mut rand_gen = StdRng::seed_from_u64(seed);
+        let mut best: f64 = 0.0;
+
+        for _ in 0..samples {
+            // x = u + i·v with |u|² + |v|² = 1.
+            let mut u: Vec<f64> = (0..n).map(|_| rand_gen.gen_range(-1.0..1.0)).collect();
+            let mut v: Vec<f64> = (0..n).map(|_| rand_gen.gen_range(-1.0..1.0)).collect();
+            let norm = u.iter().chain(&v).map(|e| e * e).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                continue;
+            }
+            u.iter_mut().chain(v.iter_mut()).for_each(|e| *e /= norm);
+
+            // xᴴ·A·x = uᵀAu + vᵀAv + i·(uᵀAv - vᵀAu)
+            let (mut re, mut im) = (0.0, 0.0);
+            for i in 0..n {
+                for j in 0..n {
+                    let a = at(i, j);
+                    re += a * (u[i] * u[j] + v[i] * v[j]);
+                    im += a * (u[i] * v[j] - v[i] * u[j]);
+                }
+            }
+
+            best = best.max(re.hypot(im));
+        }
+
+        best
+    }
+
+    /// Partitions the indices of the matrix into the groups of its diagonal blocks.
+    /// The non-zero pattern is treated as an undirected graph where indices `i` and `j`
+    /// are connected if `|a_ij|` or `|a_ji|` is greater than `eps`, and every connected
+    /// component becomes a block. Every block is sorted and blocks are ordered by
+    /// their smallest index. Returns an empty partition if the matrix is not square.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 0 2; 0 3 0; 4 0 5").unwrap();
+    /// assert_eq!(vec![vec![0, 2], vec![1]], mat.connected_blocks(0.0));
+    /// ```
+    pub fn connected_blocks(&self, eps: Entry) -> Vec<Vec<usize>> {
+        if !self.is_square() {
+            return Vec::new();
+        }
+
+        let n = self.n;
+        let mut visited = vec![false; n];
+        let mut blocks = Vec::new();
+
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+
+            visited[start] = true;
+            let mut block = vec![start];
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                for j in 0..n {
+                    if !visited[j] && (self[(i, j)].abs() > eps || self[(j, i)].abs() > eps) {
+                        visited[j] = true;
+                        block.push(j);
+                        stack.push(j);
+                    }
+                }
+            }
+
+            block.sort_unstable();
+            blocks.push(block);
+        }
+
+        blocks
+    }
+
+    /// Computes the QR decomposition of the given `n x m` matrix using Householder
+    /// reflections, returning the `n x n` orthogonal matrix `Q` and the `n x m` upper
+    /// triangular matrix `R` such that `A = Q·R`. Reflections keep the decomposition
+    /// stable for tall matrices where `n >= m`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("3 1; 4 2").unwrap();
+    /// let (q, r) = mat.qr();
+    /// assert!(q.matmul(&r).unwrap().approx_eq(&mat, 1e-5));
+    /// assert!((r[(0, 0)].abs() - 5.0).abs() < 1e-5);
+    /// assert_eq!(0.0, r[(1, 0)]);
+    /// ```
+    pub fn qr(&self) -> (Dense, Dense) {
+        let (n, m) = self.shape();
+        let mut r = self.clone();
+        let mut qt = Dense::identity(n);
+
+        for k in 0..m.min(n.saturating_sub(1)) {
+            let mut v = vec![0.0; n];
+            for (i, vi) in v.iter_mut().enumerate().skip(k) {
+                *vi = r[(i, k)];
+            }
+
+            let norm = dot(&v, &v).sqrt();
+            if norm == 0.0 {
+                continue;
+            }
+
+            // Reflecting away from the sign of the pivot avoids cancellation,
+            // which also guarantees `v` is never the zero vector.
+            v[k] += if v[k] < 0.0 { -norm } else { norm };
+            r.apply_householder(&v).ok();
+            qt.apply_householder(&v).ok();
+
+            for i in k + 1..n {
+                r[(i, k)] = 0.0;
+            }
+        }
+
+        (qt.transpose(), r)
+    }
+
+    /// Builds the `n x n` matrix `diag(d) + u·vᵀ` from the diagonal `d` and the vectors
+    /// `u` and `v` of the rank-1 update.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::diag_plus_rank1(&[1.0, 2.0], &[1.0, 1.0], &[3.0, 4.0]).unwrap();
+    /// assert_eq!(Dense::from_str("4 4; 3 6").unwrap(), mat);
+    /// ```
+    ///
+    /// # Errors
+    /// This method will return the Err variant if `diag`, `u` and `v` don't have the same len.
+    pub fn diag_plus_rank1(
+        diag: &[Entry],
+        u: &[Entry],
+        v: &[Entry],
+    ) -> Result<Dense, &'static str> {
+        let n = diag.len();
+        if u.len() != n || v.len() != n {
+            return Err("Invalid quantity of elements");
+        }
+
+        Ok(Dense::from_fn(n, n, |i, j| {
+            let d = if i == j { diag[i] } else { 0.0 };
+            d + u[i] * v[j]
+        }))
+    }
+
+    /// Computes the Cholesky decomposition of a symmetric positive-definite matrix,
+    /// returning the lower triangular matrix `L` such that `A = L·Lᵀ`.
+    /// Returns `None` if the matrix is not square, not symmetric or if a
+    /// non-positive pivot is found, meaning it is not positive-definite.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("4 2; 2 2").unwrap();
+    /// assert_eq!(Dense::from_str("2 0; 1 1").ok(), mat.cholesky());
+    /// assert_eq!(None, Dense::from_str("1 2; 2 1").unwrap().cholesky());
+    /// ```
+    pub fn cholesky(&self) -> Option<Dense> {
+        let tol = EPSILON * self.data.iter().fold(0.0, |max: Entry, e| max.max(e.abs()));
+        if !self.is_square() || !self.approx_eq(&self.transpose(), tol) {
+            return None;
+        }
+
+        let n = self.n;
+        let mut l = Dense::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                let sum = dot(&l.data[i * n..i * n + j], &l.data[j * n..j * n + j]);
+                let val = self[(i, j)] - sum;
+
+                if i == j {
+                    if val <= 0.0 {
+                        return None;
+                    }
+                    l[(i, i)] = val.sqrt();
+                } else {
+                    l[(i, j)] = val / l[(j, j)];
+                }
+            }
+        }
+
+        Some(l)
+    }
+
+    /// Computes the inverse of `A + u·vᵀ` using the Sherman-Morrison formula, where the
+    /// given matrix is `A` and `a_inv` is its inverse, without inverting the updated matrix.
+    /// Returns `None` if the shapes don't match or the denominator `1 + vᵀ·A⁻¹·u` is zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::from_str("2 0; 0 4").unwrap();
+    /// let a_inv = Dense::from_str("0.5 0; 0 0.25").unwrap();
+    ///
+    /// // A + u·vᵀ = [2 0; 2 4]
+    /// let res = a.sherman_morrison(&a_inv, &[0.0, 1.0], &[2.0, 0.0]).unwrap();
+    /// assert!(res.approx_eq(&Dense::from_str("0.5 0; -0.25 0.25").unwrap(), 1e-6));
+    /// ```
+    pub fn sherman_morrison(&self, a_inv: &Dense, u: &[Entry], v: &[Entry]) -> Option<Dense> {
+        let n = self.n;
+        if self.shape() != (n, n) || a_inv.shape() != (n, n) || u.len() != n || v.len() != n {
+            return None;
+        }
+
+        // A⁻¹·u and vᵀ·A⁻¹
+        let inv_u: Vec<Entry> = (0..n)
+            .map(|i| dot(&a_inv.data[i * n..(i + 1) * n], u))
+            .collect();
+        let v_inv: Vec<Entry> = (0..n)
+            .map(|j| (0..n).map(|i| v[i] * a_inv[(i, j)]).sum())
+            .collect();
+
+        let den = 1.0 + dot(v, &inv_u);
+        if den.abs() <= EPSILON {
+            return None;
+        }
+
+        Some(Dense::from_fn(n, n, |i, j| {
+            a_inv[(i, j)] - inv_u[i] * v_inv[j] / den
+        }))
+    }
+
+    /// Computes the principal square root of a symmetric positive-definite matrix from
+    /// its eigendecomposition `A = V·Λ·Vᵀ` as `V·Λ^(1/2)·Vᵀ`. The eigendecomposition is
+    /// found with the cyclic Jacobi method running at most `iters` sweeps until the
+    /// off-diagonal entries are below the relative tolerance `tol`.
+    /// Returns `None` if the matrix is not symmetric positive-definite or the
+    /// eigendecomposition does not converge.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("4 0; 0 9").unwrap();
+    /// let root = mat.sqrtm(50, 1e-12).unwrap();
+    /// assert!(root.approx_eq(&Dense::from_str("2 0; 0 3").unwrap(), 1e-6));
+    /// ```
+    pub fn sqrtm(&self, iters: usize, tol: f64) -> Option<Dense> {
+        if !self.is_positive_definite(0.0) {
+            return None;
+        }
+
+        let n = self.n;
+        let a = self.data.iter().map(|&e| e as f64).collect();
+        let (values, v) = eigen_symmetric(a, n, iters, tol)?;
+        if values.iter().any(|&l| l <= 0.0) {
+            return None;
+        }
+
+        let roots: Vec<f64> = values.iter().map(|l| l.sqrt()).collect();
+        Some(Dense::from_fn(n, n, |i, j| {
+            let sum: f64 = (0..n).map(|k| v[i * n + k] * roots[k] * v[j * n + k]).sum();
+            sum as Entry
+        }))
+    }
+
+    /// Computes the reduced row echelon form of the matrix using Gauss-Jordan elimination
+    /// with partial pivoting. Entries whose absolute value is below `EPSILON` times the
+    /// largest absolute entry of the matrix are considered to be zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("1 2 3; 2 4 7").unwrap();
+    /// assert_eq!(Dense::from_str("1 2 0; 0 0 1").unwrap(), mat.rref());
+    /// ```
+    pub fn rref(&self) -> Dense {
+        let (n, m) = self.shape();
+        let mut res = self.clone();
+        let tol = EPSILON * self.data.iter().fold(0.0, |max: Entry, e| max.max(e.abs()));
+
+        let mut row = 0;
+        for col in 0..m {
+            if row == n {
+                break;
+            }
+
+            let pivot = (row..n)
+                .max_by(|&x, &y| res[(x, col)].abs().total_cmp(&res[(y, col)].abs()))
+                .unwrap();
+            if res[(pivot, col)].abs() <= tol {
+                (row..n).for_each(|i| res[(i, col)] = 0.0);
+                continue;
+            }
+
+            for j in 0..m {
+                res.data.swap(row * m + j, pivot * m + j);
+            }
+
+            let p = res[(row, col)];
+            (col..m).for_each(|j| res[(row, j)] /= p);
+
+            for i in (0..n).filter(|&i| i != row) {
+                let f = res[(i, col)];
+                for j in col..m {
+                    res[(i, j)] -= f * res[(row, j)];
+                }
+            }
+
+            row += 1;
+        }
+
+        res
+    }
+
+    /// Returns the rank of the matrix, computed as the number of non-zero rows of its
+    /// reduced row echelon form.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// assert_eq!(1, Dense::from_str("1 2; 2 4").unwrap().rank());
+    /// assert_eq!(2, Dense::from_str("1 2; 3 4").unwrap().rank());
+    /// ```
+    pub fn rank(&self) -> usize {
+        let rref = self.rref();
+        (0..self.n)
+            .filter(|&i| {
+                rref.data[i * self.m..(i + 1) * self.m]
+                    .iter()
+                    .any(|&e| e != 0.0)
+            })
+            .count()
+    }
+
+    /// Whitens the matrix in-place treating every row as a sample and every column as a
+    /// variable, so that the covariance of the result is the identity. The columns are
+    /// centered and then multiplied by the inverse square root of their covariance,
+    /// found from its eigendecomposition running at most `iters` Jacobi sweeps.
+    /// Returns `None`, leaving the matrix untouched, if the covariance is singular
+    /// or its eigendecomposition does not converge.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mut data = Dense::from_str("1 0; 3 0; 1 4; 3 4").unwrap();
+    /// data.whiten(50).unwrap();
+    /// assert!(data.covariance().approx_eq(&Dense::identity(2), 1e-5));
+    /// ```
+    pub fn whiten(&mut self, iters: usize) -> Option<&mut Self> {
+        let m = self.m;
+        let cov = self.covariance();
+        let a = cov.data.iter().map(|&e| e as f64).collect();
+        let (values, v) = eigen_symmetric(a, m, iters, 1e-12)?;
+
+        let max = values.iter().fold(0.0, |max: f64, &l| max.max(l));
+        if max <= 0.0 || values.iter().any(|&l| l <= EPSILON as f64 * max) {
+            return None;
+        }
+
+        let inv_roots: Vec<f64> = values.iter().map(|l| 1.0 / l.sqrt()).collect();
+        let w = Dense::from_fn(m, m, |i, j| {
+            let sum: f64 = (0..m)
+                .map(|k| v[i * m + k] * inv_roots[k] * v[j * m + k])
+                .sum();
+            sum as Entry
+        });
+
+        self.center_columns();
+        *self = self.matmul(&w)?;
+        Some(self)
+    }
+
+    /// Computes the 2-norm condition number of the matrix as the ratio between its largest
+    /// and smallest singular values. The singular values are the square roots of the
+    /// eigenvalues of the smallest Gram matrix `Aᵀ·A` or `A·Aᵀ`, found running at most
+    /// `iters` Jacobi sweeps.
+    /// Returns `None` if the smallest singular value is zero or the eigendecomposition
+    /// does not converge.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("4 0; 0 -0.5").unwrap();
+    /// let cond = mat.cond_svd(50).unwrap();
+    /// assert!((cond - 8.0).abs() < 1e-9);
+    /// assert_eq!(None, Dense::from_str("1 2; 2 4").unwrap().cond_svd(50));
+    /// ```
+    pub fn cond_svd(&self, iters: usize) -> Option<f64> {
+        let (n, m) = self.shape();
+        let at = |i: usize, j: usize| self[(i, j)] as f64;
+        let (k, gram): (usize, Vec<f64>) = if n >= m {
+            let gram = (0..m * m)
+                .map(|x| (0..n).map(|i| at(i, x / m) * at(i, x % m)).sum())
+                .collect();
+            (m, gram)
+        } else {
+            let gram = (0..n * n)
+                .map(|x| (0..m).map(|j| at(x / n, j) * at(x % n, j)).sum())
+                .collect();
+            (n, gram)
+        };
+
+        let (values, _) = eigen_symmetric(gram, k, iters, 1e-15)?;
+        let max = values.iter().fold(0.0, |max: f64, &l| max.max(l));
+        let min = values.iter().fold(f64::INFINITY, |min: f64, &l| min.min(l));
+        if min <= f64::EPSILON * k as f64 * max {
+            return None;
+        }
+
+        Some((max / min).sqrt())
+    }
+
+    /// Computes the determinant of the given matrix using Gaussian elimination
+    /// with partial pivoting. Returns `None` if the matrix is not square.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from_str("2 1; 1 3").unwrap();
+    /// assert_eq!(Some(5.0), mat.det());
+    ///
+    /// let mat = Dense::from_str("1 2 3").unwrap();
+    /// assert_eq!(None, mat.det());
+    /// ```
+    pub fn det(&self) -> Option<Entry> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let n = self.n;
+        let mut a = self.data.clone();
+        let mut det = 1.0;
+
+        for k in 0..n {
+            let pivot =
+                (k..n).max_by(|&x, &y| a[x * n + k].abs().total_cmp(&a[y * n + k].abs()))?;
+            if a[pivot * n + k] == 0.0 {
+                return Some(0.0);
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    a.swap(k * n + j, pivot * n + j);
+                }
+                det = -det;
+            }
+
+            let p = a[k * n + k];
+            det *= p;
+
+            for i in k + 1..n {
+                let f = a[i * n + k] / p;
+                for j in k..n {
+                    a[i * n + j] -= f * a[k * n + j];
+                }
+            }
+        }
+
+        Some(det)
+    }
+
+    pub fn inv(&mut self) -> Option<&mut Self> {
+        todo!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gram_schmidt() {
+        let mat = Dense::from_str("1 1 2; 1 0 1; 0 1 1; 1 1 2").unwrap();
+        let basis = mat.gram_schmidt();
+        let (n, k) = basis.shape();
+        assert_eq!((4, 2), (n, k));
+
+        for a in 0..k {
+            for b in 0..k {
+                let dot: Entry = (0..n).map(|i| basis[(i, a)] * basis[(i, b)]).sum();
+                let expected = if a == b { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-5);
+            }
+        }
+
+        let mat = Dense::from_str("0.000001 0; 0 0.000001").unwrap();
+        assert_eq!(Dense::identity(2), mat.gram_schmidt());
+
+        let mat = Dense::from_str("0.000001 0.000002; 0.000003 0.000006").unwrap();
+        assert_eq!((2, 1), mat.gram_schmidt().shape());
+    }
+
+    #[test]
+    fn project() {
+        let proj = Dense::project(&[1.0, 2.0, 3.0], &[0.0, 0.0, 5.0]).unwrap();
+        assert_eq!(vec![0.0, 0.0, 3.0], proj);
+
+        assert!(Dense::project(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(Dense::project(&[1.0, 2.0], &[0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn vector_angle() {
+        let angle = Dense::vector_angle(&[1.0, 0.0, 0.0], &[0.0, 3.0, 0.0]).unwrap();
+        assert!((angle - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+
+        let angle = Dense::vector_angle(&[1.0, 2.0], &[2.0, 4.0]).unwrap();
+        assert!(angle.abs() < 1e-3);
+
+        assert!(Dense::vector_angle(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(Dense::vector_angle(&[1.0, 2.0], &[0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn row_cosine_similarity() {
+        let mat = Dense::from_str("1 2 3; 1 2 3; 3 0 -1; 0 0 0").unwrap();
+        assert!((mat.row_cosine_similarity(0, 1).unwrap() - 1.0).abs() < 1e-9);
+        assert!(mat.row_cosine_similarity(0, 2).unwrap().abs() < 1e-9);
+        assert_eq!(None, mat.row_cosine_similarity(0, 3));
+        assert_eq!(None, mat.row_cosine_similarity(4, 0));
+    }
+
+    #[test]
+    fn pairwise_distances() {
+        let points = Dense::from_str("0 0; 3 0; 0 4").unwrap();
+        let dist = points.pairwise_distances();
+
+        let res = Dense::from_str("0 3 4; 3 0 5; 4 5 0").unwrap();
+        assert_eq!(res, dist);
+    }
+
+    #[test]
+    fn det() {
+        let mat = Dense::from_str("0 0 0 1; 0 0 2 0; 0 3 0 0; 4 0 0 0").unwrap();
+        assert_eq!(Some(24.0), mat.det());
+
+        let mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert!(mat.det().unwrap().abs() < 1e-5);
+
+        assert_eq!(None, Dense::zeros(2, 3).det());
+    }
+
+    #[test]
+    fn to_unit_determinant() {
+        let mat = Dense::from_str("2 1 0; 1 3 1; 0 1 4").unwrap();
+        let res = mat.to_unit_determinant().unwrap();
+        assert!((res.det().unwrap() - 1.0).abs() < 1e-5);
+
+        let mat = Dense::from_str("0 1; 1 0").unwrap();
+        assert_eq!(None, mat.to_unit_determinant());
+        assert_eq!(None, Dense::zeros(2, 3).to_unit_determinant());
+    }
+
+    #[test]
+    fn matmul() {
+        let a = Dense::from_str("1 2 3; 4 5 6").unwrap();
+        let b = Dense::from_str("7 8; 9 10; 11 12").unwrap();
+
+        let res = Dense::from_str("58 64; 139 154").unwrap();
+        assert_eq!(Some(res), a.matmul(&b));
+        assert_eq!(None, a.matmul(&a));
+    }
+
+    #[test]
+    fn trace_power() {
+        let a = Dense::from_str("1 2 0; -1 3 4; 2 0 5").unwrap();
+        assert_eq!(Dense::trace_of_product(&a, &a), a.trace_power(2));
+        assert_eq!(Some(9.0), a.trace_power(1));
+        assert_eq!(None, Dense::zeros(2, 3).trace_power(2));
+    }
+
+    #[test]
+    fn to_sparse() {
+        let mut mat = Dense::zeros(4, 5);
+        mat[(0, 1)] = 3.0;
+        mat[(2, 4)] = -1.5;
+        mat[(3, 0)] = 1e-7;
+
+        let sparse = mat.to_sparse::<4, 5>(1e-6).unwrap();
+        assert_eq!(2, sparse.nnz());
+        assert_eq!(3.0, sparse[(0, 1)]);
+        assert_eq!(-1.5, sparse[(2, 4)]);
+        assert_eq!(0.0, sparse[(3, 0)]);
+
+        assert!(mat.to_sparse::<5, 4>(1e-6).is_none());
+    }
+
+    #[test]
+    fn from_npy() {
+        let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }";
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(
+            [1.0f32, -2.0, 3.5, 4.0]
+                .iter()
+                .flat_map(|e| e.to_le_bytes()),
+        );
+
+        let mat = Dense::from_npy(&bytes).unwrap();
+        assert_eq!(Dense::from_str("1 -2; 3.5 4").unwrap(), mat);
+    }
+
+    #[test]
+    fn to_npy() {
+        let mat = Dense::from_str("1.5 -2; 0 4.25; 1000 0.001").unwrap();
+        assert_eq!(mat, Dense::from_npy(&mat.to_npy()).unwrap());
+
+        let mat = Dense::zeros(0, 3);
+        assert_eq!(mat, Dense::from_npy(&mat.to_npy()).unwrap());
+    }
+
+    #[test]
+    fn kron_sum() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("5 6; 7 8").unwrap();
+
+        let res = Dense::from_str(
+            "6 6 2 0;
+             7 9 0 2;
+             3 0 9 6;
+             0 3 7 12",
+        )
+        .unwrap();
+
+        assert_eq!(Ok(res), a.kron_sum(&b));
+        assert!(a.kron_sum(&Dense::zeros(2, 3)).is_err());
+        assert!(Dense::zeros(1, 2).kron_sum(&b).is_err());
+    }
+
+    #[test]
+    fn to_string_with() {
+        let mat = Dense::from_str("123456.789 1; -0.5 22").unwrap();
+        assert_eq!(
+            "[ 123456.789      1.000 ]\n[     -0.500     22.000 ]",
+            mat.to_string_with(3, 5)
+        );
+
+        assert_eq!(
+            "[ 123457      1 ]\n[     -0     22 ]",
+            mat.to_string_with(0, 2)
+        );
+        assert_eq!(
+            "[       1       2 ]",
+            Dense::from_str("1 2").unwrap().to_string_with(0, 7)
+        );
+    }
+
+    #[test]
+    fn vec() {
+        let mat = Dense::from_str("1 2 3; 4 5 6").unwrap();
+        let v = mat.vec();
+        assert_eq!(vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0], v);
+        assert_eq!(Ok(mat), Dense::unvec(&v, 2, 3));
+
+        let mat = Dense::from_str("1; 2; 3").unwrap();
+        assert_eq!(Ok(mat), Dense::unvec(&[1.0, 2.0, 3.0], 3, 1));
+
+        assert!(Dense::unvec(&v, 2, 2).is_err());
+    }
+
+    #[test]
+    fn commutator() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        assert_eq!(Ok(Dense::zeros(2, 2)), a.commutator(&a));
+
+        let b = Dense::from_str("0 1; 1 0").unwrap();
+        let res = Dense::from_str("-1 -3; 3 1").unwrap();
+        assert_eq!(Ok(res), a.commutator(&b));
+
+        assert!(a.commutator(&Dense::zeros(3, 3)).is_err());
+        assert!(Dense::zeros(2, 3).commutator(&Dense::zeros(2, 3)).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let mat = Dense::from_str("1 -12345.678; 0.5 inf").unwrap();
+        let s = mat.to_string();
+        assert_eq!(
+            "[      1.0000 -12345.6777 ]\n[      0.5000         inf ]",
+            s
+        );
+
+        let widths: Vec<usize> = s.lines().map(str::len).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]));
+    }
+
+    #[test]
+    fn commutes_with() {
+        let a = Dense::from_str("1 0 0; 0 2 0; 0 0 3").unwrap();
+        let b = Dense::from_str("4 0 0; 0 5 0; 0 0 6").unwrap();
+        assert!(a.commutes_with(&b, 0.0));
+
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("0 1; 1 0").unwrap();
+        assert!(!a.commutes_with(&b, 1e-3));
+
+        assert!(!a.commutes_with(&Dense::zeros(3, 3), 1e-3));
+    }
+
+    #[test]
+    fn row_col() {
+        let mut mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert_eq!(Some(vec![1.0, 2.0, 3.0]), mat.row(0));
+        assert_eq!(Some(vec![7.0, 8.0, 9.0]), mat.row(2));
+        assert_eq!(Some(vec![1.0, 4.0, 7.0]), mat.col(0));
+        assert_eq!(Some(vec![3.0, 6.0, 9.0]), mat.col(2));
+        assert_eq!(None, mat.row(3));
+        assert_eq!(None, mat.col(3));
+
+        assert!(mat.set_row(1, &[0.0, -1.0, -2.0]).is_ok());
+        assert_eq!(Some(vec![2.0, -1.0, 8.0]), mat.col(1));
+
+        assert!(mat.set_row(3, &[0.0, 0.0, 0.0]).is_err());
+        assert!(mat.set_row(0, &[0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn expm() {
+        let diag = [1.0, -2.0, 3.5];
+        let mut mat = Dense::zeros(3, 3);
+        for (i, &d) in diag.iter().enumerate() {
+            mat[(i, i)] = d;
+        }
+
+        let res = mat.expm(20).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { diag[i].exp() } else { 0.0 };
+                assert!((res[(i, j)] - expected).abs() <= 1e-4 * expected.max(1.0));
+            }
+        }
+
+        // The exponential of a nilpotent matrix has a finite series.
+        let mat = Dense::from_str("0 1; 0 0").unwrap();
+        let res = Dense::from_str("1 1; 0 1").unwrap();
+        assert_eq!(Some(res), mat.expm(10));
+
+        assert_eq!(None, Dense::zeros(2, 3).expm(10));
+
+        let mat = Dense::from_str("1 inf; 0 1").unwrap();
+        assert_eq!(None, mat.expm(10));
+    }
+
+    #[test]
+    fn trace() {
+        let mut mat = Dense::zeros(4, 4);
+        (0..4).for_each(|i| mat[(i, i)] = 1.0);
+        assert_eq!(Some(4.0), mat.trace());
+
+        let mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert_eq!(Some(15.0), mat.trace());
+
+        assert_eq!(None, Dense::zeros(2, 3).trace());
+    }
+
+    #[test]
+    fn identity() {
+        let mat = Dense::identity(3);
+        assert_eq!(Dense::from_str("1 0 0; 0 1 0; 0 0 1").unwrap(), mat);
+        assert_eq!((0, 0), Dense::identity(0).shape());
+    }
+
+    #[test]
+    fn from_fn() {
+        let mat = Dense::from_fn(3, 4, |i, j| (i * 10 + j) as f32);
+        assert_eq!((3, 4), mat.shape());
+        assert_eq!(0.0, mat[(0, 0)]);
+        assert_eq!(3.0, mat[(0, 3)]);
+        assert_eq!(12.0, mat[(1, 2)]);
+        assert_eq!(23.0, mat[(2, 3)]);
+    }
+
+    #[test]
+    fn givens() {
+        let rot = Dense::givens(4, 1, 3, 0.7).unwrap();
+
+        for a in 0..4 {
+            for b in 0..4 {
+                let dot: Entry = (0..4).map(|k| rot[(k, a)] * rot[(k, b)]).sum();
+                let expected = if a == b { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-6);
+            }
+        }
+
+        let x = Dense::from_str("1; 2; 3; 4").unwrap();
+        let y = rot.matmul(&x).unwrap();
+        let (sin, cos) = (0.7 as Entry).sin_cos();
+        assert_eq!(1.0, y[(0, 0)]);
+        assert_eq!(3.0, y[(2, 0)]);
+        assert!((y[(1, 0)] - (2.0 * cos - 4.0 * sin)).abs() < 1e-6);
+        assert!((y[(3, 0)] - (2.0 * sin + 4.0 * cos)).abs() < 1e-6);
+
+        assert!(Dense::givens(3, 0, 3, 0.7).is_err());
+        assert!(Dense::givens(3, 1, 1, 0.7).is_err());
+    }
+
+    #[test]
+    fn from_array() {
+        let mat = Dense::from([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!((2, 2), mat.shape());
+        assert_eq!(2.0, mat[(0, 1)]);
+        assert_eq!(3.0, mat[(1, 0)]);
+
+        let mat = Dense::from([[5.0, 6.0, 7.0]]);
+        assert_eq!((1, 3), mat.shape());
+        assert_eq!(5.0, mat[(0, 0)]);
+        assert_eq!(7.0, mat[(0, 2)]);
+    }
+
+    #[test]
+    fn apply_householder() {
+        let mut mat = Dense::from([[2.0, 1.0], [1.0, 3.0], [2.0, 5.0]]);
+
+        // v = x - |x| e1 maps the first column onto the first axis.
+        let v = [2.0 - 3.0, 1.0, 2.0];
+        mat.apply_householder(&v).unwrap();
+
+        assert!((mat[(0, 0)] - 3.0).abs() < 1e-6);
+        assert!(mat[(1, 0)].abs() < 1e-6);
+        assert!(mat[(2, 0)].abs() < 1e-6);
+
+        // Reflections preserve the norm of every column.
+        let norm: Entry = (0..3).map(|i| mat[(i, 1)].powi(2)).sum();
+        assert!((norm - 35.0).abs() < 1e-4);
+
+        assert!(mat.apply_householder(&[1.0, 0.0]).is_err());
+        assert!(mat.apply_householder(&[0.0, 0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn bandwidth() {
+        let mat = Dense::from_fn(5, 5, |i, j| if i.abs_diff(j) <= 1 { 2.0 } else { 1e-9 });
+        assert_eq!((1, 1), mat.bandwidth(1e-6));
+        assert_eq!((4, 4), mat.bandwidth(0.0));
+
+        let mat = Dense::from_fn(4, 6, |i, j| (i + j + 1) as Entry);
+        assert_eq!((3, 5), mat.bandwidth(0.0));
+
+        assert_eq!((0, 0), Dense::identity(3).bandwidth(0.0));
+    }
+
+    #[test]
+    fn truncate_to_band() {
+        let mut mat = Dense::from_fn(4, 5, |i, j| (i * 5 + j + 1) as Entry);
+        mat.truncate_to_band(1, 2);
+
+        for i in 0..4 {
+            for j in 0..5 {
+                let in_band = j + 1 >= i && j <= i + 2;
+                let expected = if in_band {
+                    (i * 5 + j + 1) as Entry
+                } else {
+                    0.0
+                };
+                assert_eq!(expected, mat[(i, j)]);
+            }
+        }
+
+        assert_eq!((1, 2), mat.bandwidth(0.0));
+    }
+
+    #[test]
+    fn apply_indexed() {
+        let mut mat = Dense::from_fn(3, 3, |i, j| (i * 3 + j + 1) as Entry);
+        mat.apply_indexed(|(i, j), v| if i > j { 0.0 } else { v });
+
+        let res = Dense::from_str("1 2 3; 0 5 6; 0 0 9").unwrap();
+        assert_eq!(res, mat);
+    }
+
+    #[test]
+    fn weighted_sum() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("4 0; -2 8").unwrap();
+
+        let res = Dense::from_str("2.5 1; 0.5 6").unwrap();
+        assert_eq!(Ok(res), Dense::weighted_sum(&[(&a, 0.5), (&b, 0.5)]));
+
+        assert!(Dense::weighted_sum(&[]).is_err());
+        assert!(Dense::weighted_sum(&[(&a, 1.0), (&Dense::zeros(2, 3), 1.0)]).is_err());
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("1.0000001 2; 3 3.9999998").unwrap();
+        assert!(a.approx_eq(&b, 1e-6));
+        assert!(!a.approx_eq(&b, 0.0));
+        assert!(a.approx_eq(&a, 0.0));
+
+        assert!(!a.approx_eq(&Dense::zeros(2, 3), 1e6));
+    }
+
+    #[test]
+    fn clone() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let mut b = a.clone();
+        b.scalar_mul(2.0);
+
+        assert_eq!(Dense::from_str("1 2; 3 4").unwrap(), a);
+        assert_eq!(Dense::from_str("2 4; 6 8").unwrap(), b);
+    }
+
+    #[test]
+    fn moving_average_rows() {
+        let mat = Dense::from_str("3 0; 6 3; 9 6; 0 9; 3 3").unwrap();
+        let res = Dense::from_str("3 0; 4.5 1.5; 6 3; 5 6; 4 6").unwrap();
+        assert_eq!(Ok(res), mat.moving_average_rows(3));
+
+        assert_eq!(Ok(mat.clone()), mat.moving_average_rows(1));
+        assert!(mat.moving_average_rows(0).is_err());
+        assert!(mat.moving_average_rows(6).is_err());
+    }
+
+    #[test]
+    fn one_hot() {
+        let mat = Dense::one_hot(&[2, 0, 3, 2], 4).unwrap();
+        let res = Dense::from_str("0 0 1 0; 1 0 0 0; 0 0 0 1; 0 0 1 0").unwrap();
+        assert_eq!(res, mat);
+
+        assert!(Dense::one_hot(&[0, 4], 4).is_err());
+        assert_eq!(Ok(Dense::zeros(0, 2)), Dense::one_hot(&[], 2));
+    }
+
+    #[test]
+    fn argmax_rows() {
+        let mat = Dense::from_str("1 5 3; -1 -2 -0.5; 9 0 8").unwrap();
+        assert_eq!(vec![1, 2, 0], mat.argmax_rows());
+
+        let mat = Dense::from_str("2 7 7; 4 4 4").unwrap();
+        assert_eq!(vec![1, 0], mat.argmax_rows());
+    }
+
+    #[test]
+    #[should_panic]
+    fn argmax_rows_no_columns() {
+        Dense::zeros(2, 0).argmax_rows();
+    }
+
+    #[test]
+    fn solve() {
+        let mat = Dense::from_str("3 2; 1 -4").unwrap();
+        let x = mat.solve(&[7.0, -7.0]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-6);
+        assert!((x[1] - 2.0).abs() < 1e-6);
+
+        let mat = Dense::from_str("0 2 1; 1 -1 3; 4 1 -2").unwrap();
+        let b = [5.0, 2.5, -1.0];
+        let x = mat.solve(&b).unwrap();
+        for (i, bi) in b.iter().enumerate() {
+            let residual = dot(&mat.row(i).unwrap(), &x) - bi;
+            assert!(residual.abs() < 1e-5);
+        }
+
+        let singular = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert_eq!(None, singular.solve(&[1.0, 2.0, 3.0]));
+        assert_eq!(None, mat.solve(&[1.0, 2.0]));
+        assert_eq!(None, Dense::zeros(2, 3).solve(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn covariance() {
+        let samples = Dense::from_str("2 1 0; 4 3 1; 6 2 2; 8 6 5").unwrap();
+
+        // Means: (5, 3, 2)
+        let res = Dense::from_str(
+            "6.6666665 4.6666665 5.3333335;
+             4.6666665 4.6666665 4.3333335;
+             5.3333335 4.3333335 4.6666665",
+        )
+        .unwrap();
+        assert!(res.approx_eq(&samples.covariance(), 1e-5));
+
+        let single = Dense::from_str("1 2 3").unwrap();
+        assert_eq!(Dense::zeros(3, 3), single.covariance());
+    }
+
+    #[test]
+    fn stack() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("5 6; 7 8").unwrap();
+
+        let res = Dense::from_str("1 2 5 6; 3 4 7 8").unwrap();
+        assert_eq!(Some(res), a.hstack(&b));
+
+        let res = Dense::from_str("1 2; 3 4; 5 6; 7 8").unwrap();
+        assert_eq!(Some(res), a.vstack(&b));
+
+        assert_eq!(None, a.hstack(&Dense::zeros(3, 2)));
+        assert_eq!(None, a.vstack(&Dense::zeros(2, 3)));
+    }
+
+    #[test]
+    fn submatrix() {
+        let mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert_eq!(Dense::from_str("5 6; 8 9").ok(), mat.submatrix(1..3, 1..3));
+        assert_eq!(Dense::from_str("1 2; 4 5").ok(), mat.submatrix(0..2, 0..2));
+        assert_eq!(Some(Dense::zeros(0, 3)), mat.submatrix(1..1, 0..3));
+
+        assert_eq!(None, mat.submatrix(2..4, 0..2));
+        assert_eq!(None, mat.submatrix(0..2, 1..4));
+    }
+
+    #[test]
+    fn center_columns() {
+        let mut mat = Dense::from_str("1 10 -3; 2 20 0; 6 60 9").unwrap();
+        mat.center_columns();
+
+        for j in 0..3 {
+            let mean: Entry = mat.col(j).unwrap().iter().sum::<Entry>() / 3.0;
+            assert!(mean.abs() < 1e-5);
+        }
+
+        assert_eq!(Dense::from_str("-2; -1; 3").ok(), mat.submatrix(0..3, 0..1));
+    }
+
+    #[test]
+    fn hadamard() {
+        let a = Dense::from_str("1 -2; 0.5 4").unwrap();
+        let b = Dense::from_str("3 3; 4 -0.25").unwrap();
+        assert_eq!(Dense::from_str("3 -6; 2 -1").ok(), a.hadamard(&b));
+
+        assert_eq!(None, a.hadamard(&Dense::zeros(2, 3)));
+    }
+
+    #[test]
+    fn pca() {
+        // Every sample lies on the line spanned by (1, 2, -1).
+        let data = Dense::from_fn(5, 3, |i, j| {
+            let t = i as Entry - 1.5;
+            [1.0, 2.0, -1.0][j] * t + [3.0, -1.0, 0.5][j]
+        });
+
+        let proj = data.pca(1, 100).unwrap();
+        assert_eq!((5, 1), proj.shape());
+
+        let total: Entry = (0..3).map(|j| data.covariance()[(j, j)]).sum();
+        let captured = proj.covariance()[(0, 0)];
+        assert!((total - captured).abs() < 1e-3 * total);
+
+        assert_eq!(None, data.pca(4, 100));
+    }
+
+    #[test]
+    fn operators() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let b = Dense::from_str("5 6; 7 8").unwrap();
+
+        assert_eq!(Dense::from_str("6 8; 10 12").unwrap(), &a + &b);
+        assert_eq!(Dense::from_str("-4 -4; -4 -4").unwrap(), &a - &b);
+        assert_eq!(Dense::from_str("19 22; 43 50").unwrap(), &a * &b);
+        assert_eq!(Dense::from_str("0.5 1; 1.5 2").unwrap(), &a * 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn operators_shape_mismatch() {
+        let a = Dense::from_str("1 2; 3 4").unwrap();
+        let _ = &a + &Dense::zeros(2, 3);
+    }
+
+    #[test]
+    fn frobenius_inner() {
+        let a = Dense::from_str("1 -2 3; 0 4 0.5").unwrap();
+        let b = Dense::from_str("2 1 -1; 3 0.5 6").unwrap();
+
+        let expected = Dense::trace_of_product(&a, &b.transpose());
+        assert_eq!(Some(2.0), a.frobenius_inner(&b));
+        assert_eq!(expected, a.frobenius_inner(&b));
+
+        assert_eq!(None, a.frobenius_inner(&b.transpose()));
+    }
+
+    #[test]
+    fn csv() {
+        let mat = Dense::from_str("1 -2.25 3; 0.1 1e6 0").unwrap();
+        let csv = mat.to_csv();
+        assert_eq!("1,-2.25,3\n0.1,1000000,0\n", csv);
+        assert_eq!(Ok(mat.clone()), Dense::from_csv(&csv));
+        assert_eq!(Ok(mat), Dense::from_csv(csv.trim_end()));
+
+        assert!(Dense::from_csv("1,2\n3,4,5\n").is_err());
+        assert!(Dense::from_csv("").is_err());
+    }
+
+    #[test]
+    fn solve_sylvester() {
+        let a = Dense::from_str("2 1; 0 3").unwrap();
+        let b = Dense::from_str("1 0 2; -1 4 0; 0 1 1").unwrap();
+        let x = Dense::from_str("1 -1 0.5; 2 0 3").unwrap();
+        let c = &(&a * &x) + &(&x * &b);
+
+        let res = Dense::solve_sylvester(&a, &b, &c).unwrap();
+        assert!(res.approx_eq(&x, 1e-4));
+
+        assert_eq!(None, Dense::solve_sylvester(&a, &b, &x.transpose()));
+
+        // A and -B share an eigenvalue, so the system is singular.
+        let b = Dense::from_str("-2").unwrap();
+        assert_eq!(None, Dense::solve_sylvester(&a, &b, &Dense::zeros(2, 1)));
+    }
+
+    #[test]
+    fn generic_entries() {
+        let a = Dense::<f64>::from_str("0.5 2; -1 4").unwrap();
+        let b = Dense::<f64>::from_str("2; 0.25").unwrap();
+        assert_eq!(Dense::<f64>::from_str("1.5; -1").ok(), a.matmul(&b));
+
+        let a = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+        let b = Dense::from([[1, 0], [0, 1], [-1, 2]]);
+        assert_eq!(Dense::from([[-2, 8], [-2, 17]]), &a * &b);
+        assert_eq!(Some(6), Dense::<i32>::identity(6).trace());
+    }
+
+    #[test]
+    fn solve_lyapunov() {
+        let a = Dense::from_str("0.5 0.2; -0.1 0.3").unwrap();
+        let q = Dense::from_str("2 1; 1 3").unwrap();
+
+        let x = Dense::solve_lyapunov(&a, &q).unwrap();
+        let res = &(&(&a * &x) * &a.transpose()) - &x;
+        assert!((&res + &q).approx_eq(&Dense::zeros(2, 2), 1e-4));
+        assert!(x.approx_eq(&x.transpose(), 1e-4));
+
+        assert_eq!(None, Dense::solve_lyapunov(&a, &Dense::zeros(3, 3)));
+
+        // An eigenvalue of magnitude 2 makes the system unstable.
+        let a = Dense::from_str("2 0; 0 0.5").unwrap();
+        assert_eq!(None, Dense::solve_lyapunov(&a, &q));
+    }
+
+    #[test]
+    fn is_positive_definite() {
+        let spd = Dense::from_str("4 12 -16; 12 37 -43; -16 -43 98").unwrap();
+        assert!(spd.is_positive_definite(1e-9));
+        assert!(Dense::identity(4).is_positive_definite(1e-9));
+
+        let indefinite = Dense::from_str("2 0 0; 0 -1 0; 0 0 3").unwrap();
+        assert!(!indefinite.is_positive_definite(1e-9));
+
+        let semidefinite = Dense::from_str("1 1; 1 1").unwrap();
+        assert!(!semidefinite.is_positive_definite(1e-9));
+
+        assert!(!Dense::from_str("2 1; 0 2")
+            .unwrap()
+            .is_positive_definite(1e-9));
+        assert!(!Dense::from_str("1 0").unwrap().is_positive_definite(1e-9));
+    }
+
+    #[test]
+    fn structure_predicates() {
+        let sym = Dense::from_str("1 2 3; 2 5 -1; 3 -1 0").unwrap();
+        assert!(sym.is_symmetric());
+        assert!(!sym.is_diagonal(1e-3));
+
+        let non_sym = Dense::<i32>::from_str("1 2 3; 2 5 -1; 3 1 0").unwrap();
+        assert!(!non_sym.is_symmetric());
+        assert!(!Dense::<i32>::from_str("1 2").unwrap().is_symmetric());
+
+        let near_diag = Dense::from_str("3 1e-7 0; -2e-7 4 0; 0 0 5").unwrap();
+        assert!(near_diag.is_diagonal(1e-6));
+        assert!(!near_diag.is_diagonal(1e-8));
+        assert!(!near_diag.is_symmetric());
+        assert!(Dense::from_str("1 0 0; 0 2 0").unwrap().is_diagonal(0.0));
+    }
+
+    #[test]
+    fn jacobi_scale() {
+        let mat = Dense::from_str("16 2 -4; 2 0.25 1; -4 1 100").unwrap();
+        let (scaled, d) = mat.jacobi_scale().unwrap();
+        assert_eq!(vec![4.0, 0.5, 10.0], d);
+        assert!((0..3).all(|i| (scaled[(i, i)] - 1.0).abs() < 1e-6));
+        assert!((scaled[(0, 1)] - 1.0).abs() < 1e-6);
+
+        let unscaled = Dense::from_fn(3, 3, |i, j| scaled[(i, j)] * d[i] * d[j]);
+        assert!(unscaled.approx_eq(&mat, 1e-4));
+
+        assert_eq!(None, Dense::from_str("1 2; 2 0").unwrap().jacobi_scale());
+        assert_eq!(None, Dense::from_str("1 2").unwrap().jacobi_scale());
+    }
+
+    #[test]
+    fn pow() {
+        let rot = Dense::<i32>::from_str("0 -1; 1 0").unwrap();
+        assert_eq!(Some(Dense::<i32>::identity(2)), rot.pow(0));
+        assert_eq!(Some(rot.clone()), rot.pow(1));
+        assert_eq!(Dense::<i32>::from_str("-1 0; 0 -1").ok(), rot.pow(2));
+        assert_eq!(rot.pow(3), rot.pow(1003));
+        assert_eq!(Some(Dense::<i32>::identity(2)), rot.pow(1_000_000));
+
+        let mat = Dense::from_str("0.5 0.5; 0.25 0.75").unwrap();
+        let res = mat.pow(5).unwrap();
+        let expected = (0..5).fold(Dense::identity(2), |acc, _| &acc * &mat);
+        assert!(res.approx_eq(&expected, 1e-6));
+
+        assert_eq!(None, Dense::<i32>::zeros(3, 2).pow(0));
+    }
+
+    #[test]
+    fn kron() {
+        let a = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+        let res = Dense::<i32>::identity(2).kron(&a);
+        assert_eq!((4, 6), res.shape());
+        assert_eq!(
+            Dense::<i32>::from_str("1 2 3 0 0 0; 4 5 6 0 0 0; 0 0 0 1 2 3; 0 0 0 4 5 6").unwrap(),
+            res
+        );
+
+        let b = Dense::<i32>::from_str("0 1; -1 0").unwrap();
+        let res = a.kron(&b);
+        assert_eq!((4, 6), res.shape());
+        assert_eq!(-6, res[(3, 4)]);
+        assert_eq!(5, res[(2, 3)]);
+
+        assert_eq!((0, 6), Dense::<i32>::zeros(0, 2).kron(&a).shape());
+    }
+
+    #[test]
+    fn numerical_radius_estimate() {
+        // A rotation of diag(3, -1), so it is normal with spectral radius 3.
+        let mat = Dense::from_str("1 2; 2 1").unwrap();
+        let r = mat.numerical_radius_estimate(5000, 1);
+        assert!(r <= 3.0 + 1e-6);
+        assert!((r - 3.0).abs() < 0.05);
+
+        // The rotation by 90 degrees has eigenvalues ±i.
+        let rot = Dense::from_str("0 -1; 1 0").unwrap();
+        let r = rot.numerical_radius_estimate(5000, 2);
+        assert!(r <= 1.0 + 1e-6);
+        assert!((r - 1.0).abs() < 0.05);
+
+        assert_eq!(0.0, mat.numerical_radius_estimate(0, 0));
+        assert_eq!(0.0, Dense::zeros(2, 3).numerical_radius_estimate(10, 0));
+    }
+
+    #[test]
+    fn connected_blocks() {
+        let mat = Dense::from_str("1 2 0 0; 3 4 0 0; 0 0 5 6; 0 0 7 8").unwrap();
+        assert_eq!(vec![vec![0, 1], vec![2, 3]], mat.connected_blocks(0.0));
+
+        // A tiny coupling is ignored under a large enough tolerance.
+        let mut coupled = mat.clone();
+        coupled[(3, 0)] = 1e-4;
+        assert_eq!(vec![vec![0, 1, 2, 3]], coupled.connected_blocks(0.0));
+        assert_eq!(vec![vec![0, 1], vec![2, 3]], coupled.connected_blocks(1e-3));
+
+        let dense = Dense::from_str("1 2; 3 4").unwrap();
+        assert_eq!(vec![vec![0, 1]], dense.connected_blocks(0.0));
+        assert!(Dense::zeros(2, 3).connected_blocks(0.0).is_empty());
+    }
+
+    #[test]
+    fn lu() {
+        // The zero leading entry forces a row swap on the first step.
+        let mat = Dense::from_str("0 2 1 3; 4 1 0 2; 1 3 5 0; 2 0 1 6").unwrap();
+        let (l, u, perm) = mat.lu().unwrap();
+        assert_ne!(0, perm[0]);
+
+        let pa = Dense::from_fn(4, 4, |i, j| mat[(perm[i], j)]);
+        assert!(pa.approx_eq(&l.matmul(&u).unwrap(), 1e-5));
+        assert!((0..4).all(|i| l[(i, i)] == 1.0 && (i + 1..4).all(|j| l[(i, j)] == 0.0)));
+        assert!((0..4).all(|i| (0..i).all(|j| u[(i, j)] == 0.0)));
+
+        let mut sorted = perm.clone();
+        sorted.sort_unstable();
+        assert_eq!(vec![0, 1, 2, 3], sorted);
+
+        assert_eq!(None, Dense::from_str("1 2; 2 4").unwrap().lu());
+        assert_eq!(None, Dense::zeros(2, 3).lu());
+    }
+
+    #[test]
+    fn qr() {
+        let mat = Dense::from_str("12 -51 4; 6 167 -68; -4 24 -41; 1 2 3").unwrap();
+        let (q, r) = mat.qr();
+        assert_eq!((4, 4), q.shape());
+        assert_eq!((4, 3), r.shape());
+
+        let qtq = q.transpose().matmul(&q).unwrap();
+        assert!(qtq.approx_eq(&Dense::identity(4), 1e-5));
+        assert!(q.matmul(&r).unwrap().approx_eq(&mat, 1e-3));
+        assert!((0..4).all(|i| (0..i.min(3)).all(|j| r[(i, j)] == 0.0)));
+
+        // Rank deficient columns still reconstruct the input.
+        let mat = Dense::from_str("1 2; 2 4; 3 6").unwrap();
+        let (q, r) = mat.qr();
+        assert!(q.matmul(&r).unwrap().approx_eq(&mat, 1e-5));
+        assert!(r[(1, 1)].abs() < 1e-5);
+    }
+
+    #[test]
+    fn diag_plus_rank1() {
+        let mat =
+            Dense::diag_plus_rank1(&[1.0, -2.0, 0.5], &[1.0, 0.0, 2.0], &[3.0, 1.0, -1.0]).unwrap();
+        let res = Dense::from_str("4 1 -1; 0 -2 0; 6 2 -1.5").unwrap();
+        assert_eq!(res, mat);
+
+        assert_eq!(
+            Ok(Dense::zeros(0, 0)),
+            Dense::diag_plus_rank1(&[], &[], &[])
+        );
+        assert!(Dense::diag_plus_rank1(&[1.0, 2.0], &[1.0], &[1.0, 2.0]).is_err());
+        assert!(Dense::diag_plus_rank1(&[1.0, 2.0], &[1.0, 2.0], &[1.0]).is_err());
+    }
+
+    #[test]
+    fn cholesky() {
+        let mat = Dense::from_str("4 12 -16; 12 37 -43; -16 -43 98").unwrap();
+        let l = mat.cholesky().unwrap();
+        assert!(l.approx_eq(&Dense::from_str("2 0 0; 6 1 0; -8 5 3").unwrap(), 1e-4));
+        assert!(l.matmul(&l.transpose()).unwrap().approx_eq(&mat, 1e-3));
+
+        let indefinite = Dense::from_str("1 2 0; 2 1 0; 0 0 1").unwrap();
+        assert_eq!(None, indefinite.cholesky());
+        assert_eq!(None, Dense::from_str("2 1; 0 2").unwrap().cholesky());
+        assert_eq!(None, Dense::from_str("1 0").unwrap().cholesky());
+    }
+
+    #[test]
+    fn sherman_morrison() {
+        let a = Dense::from_str("4 1 0; 1 3 1; 0 1 2").unwrap();
+        let inverse = |mat: &Dense| {
+            let cols: Vec<_> = (0..3)
+                .map(|j| mat.solve(&Dense::identity(3).col(j).unwrap()).unwrap())
+                .collect();
+            Dense::from_fn(3, 3, |i, j| cols[j][i])
+        };
+
+        let (u, v) = ([1.0, 0.0, 2.0], [0.5, -1.0, 1.0]);
+        let res = a.sherman_morrison(&inverse(&a), &u, &v).unwrap();
+
+        let updated = &a + &Dense::diag_plus_rank1(&[0.0; 3], &u, &v).unwrap();
+        assert!(res.approx_eq(&inverse(&updated), 1e-5));
+
+        // A + u·vᵀ is singular when vᵀ·A⁻¹·u = -1.
+        let a = Dense::identity(2);
+        assert_eq!(None, a.sherman_morrison(&a, &[1.0, 0.0], &[-1.0, 0.0]));
+        assert_eq!(None, a.sherman_morrison(&a, &[1.0], &[1.0, 0.0]));
+    }
+
+    #[test]
+    fn sqrtm() {
+        let mat = Dense::from_str("4 12 -16; 12 37 -43; -16 -43 98").unwrap();
+        let root = mat.sqrtm(100, 1e-12).unwrap();
+        assert!(root.approx_eq(&root.transpose(), 1e-4));
+        assert!(root.is_positive_definite(0.0));
+        assert!((&root * &root).approx_eq(&mat, 1e-3));
+
+        let indefinite = Dense::from_str("1 2; 2 1").unwrap();
+        assert_eq!(None, indefinite.sqrtm(100, 1e-12));
+        assert_eq!(None, Dense::from_str("1 2; 0 1").unwrap().sqrtm(100, 1e-12));
+
+        // Not enough sweeps to converge.
+        assert_eq!(None, mat.sqrtm(0, 1e-12));
+    }
+
+    #[test]
+    fn rref_rank() {
+        let deficient = Dense::from_str("1 2 3; 2 4 6; 1 0 1").unwrap();
+        let rref = deficient.rref();
+        assert!(rref.approx_eq(&Dense::from_str("1 0 1; 0 1 1; 0 0 0").unwrap(), 1e-6));
+        assert_eq!(2, deficient.rank());
+
+        let full = Dense::from_str("2 1 0; 1 3 1; 0 1 4").unwrap();
+        assert!(full.rref().approx_eq(&Dense::identity(3), 1e-6));
+        assert_eq!(3, full.rank());
+
+        let wide = Dense::from_str("0 1 2 3; 0 2 4 7").unwrap();
+        assert_eq!(Dense::from_str("0 1 2 0; 0 0 0 1").unwrap(), wide.rref());
+        assert_eq!(2, wide.rank());
+        assert_eq!(0, Dense::zeros(3, 2).rank());
+    }
+
+    #[test]
+    fn dot_vectors() {
+        let row = Dense::from_str("1 -2 0.5").unwrap();
+        let col = Dense::from_str("2; 3; 4").unwrap();
+        assert_eq!(Some(-2.0), row.dot(&col));
+        assert_eq!(Some(-2.0), col.dot(&row));
+        assert_eq!(Some(29.0), col.dot(&col));
+
+        let square = Dense::from_str("1 2; 3 4").unwrap();
+        assert_eq!(None, square.dot(&square));
+        assert_eq!(None, row.dot(&Dense::from_str("1 2").unwrap()));
+    }
+
+    #[test]
+    fn whiten() {
+        let mut data = Dense::from_str("2 1 0; 4 3 1; 1 -1 2; 5 2 2; 3 0 -1; 0 1 1").unwrap();
+        data.whiten(100).unwrap();
+        assert!(data.covariance().approx_eq(&Dense::identity(3), 1e-4));
+        assert!((0..3).all(|j| data.col(j).unwrap().iter().sum::<Entry>().abs() < 1e-4));
+
+        // The second column is a multiple of the first one.
+        let mut singular = Dense::from_str("1 2; 2 4; 3 6").unwrap();
+        assert!(singular.whiten(100).is_none());
+        assert_eq!(Dense::from_str("1 2; 2 4; 3 6").unwrap(), singular);
+    }
+
+    #[test]
+    fn cond_svd() {
+        // Symmetric positive-definite, so its condition number is the ratio of its
+        // eigenvalues 3 and 1.
+        let mat = Dense::from_str("2 1; 1 2").unwrap();
+        assert!((mat.cond_svd(50).unwrap() - 3.0).abs() < 1e-6);
+
+        let q = Dense::givens(3, 0, 2, 0.3).unwrap();
+        assert!((q.cond_svd(50).unwrap() - 1.0).abs() < 1e-5);
+
+        // Rectangular matrices with singular values 1 and 2.
+        let tall = Dense::from_str("1 0; 0 2; 0 0").unwrap();
+        assert!((tall.cond_svd(50).unwrap() - 2.0).abs() < 1e-9);
+        assert!((tall.transpose().cond_svd(50).unwrap() - 2.0).abs() < 1e-9);
+
+        assert_eq!(None, Dense::zeros(2, 2).cond_svd(50));
+        assert_eq!(
+            None,
+            Dense::from_str("1 2 3; 2 4 6; 0 1 0").unwrap().cond_svd(50)
+        );
+    }
+
+    #[test]
+    fn rand_orthogonal() {
+        let q = Dense::rand_orthogonal(6, 7);
+        assert_eq!((6, 6), q.shape());
+        assert!(q.is_orthogonal(1e-5));
+        assert!(q.transpose().is_orthogonal(1e-5));
+
+        assert_eq!(q, Dense::rand_orthogonal(6, 7));
+        assert_ne!(q, Dense::rand_orthogonal(6, 8));
+        assert!(!Dense::from_str("1 0 0; 0 1 0").unwrap().is_orthogonal(1e-5));
+    }
+
+    #[test]
+    fn rand_rank() {
+        for rank in 0..=4 {
+            let mat = Dense::rand_rank(6, 4, rank, rank as u64);
+            assert_eq!((6, 4), mat.shape());
+            assert_eq!(rank, mat.rank());
+        }
+
+        assert_eq!(Dense::rand_rank(3, 5, 2, 1), Dense::rand_rank(3, 5, 2, 1));
+        assert_eq!(3, Dense::rand_rank(3, 5, 3, 9).rank());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rand_rank_too_large() {
+        Dense::rand_rank(3, 2, 3, 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_matmul() {
+        let a = Dense::rand(64, 64);
+        let b = Dense::rand(64, 64);
+        let res = a.par_matmul(&b).unwrap();
+        assert!(res.approx_eq(&a.matmul(&b).unwrap(), 1e-5));
+
+        assert_eq!(None, a.par_matmul(&Dense::zeros(3, 64)));
+        assert_eq!(
+            Some(Dense::zeros(64, 0)),
+            a.par_matmul(&Dense::zeros(64, 0))
+        );
+    }
+
+    #[test]
+    fn matmul_blocked() {
+        let a = Dense::rand(100, 100);
+        let b = Dense::rand(100, 100);
+        let res = a.matmul(&b).unwrap();
+        for block in [1, 7, 32, 100, 128] {
+            assert_eq!(Some(&res), a.matmul_blocked(&b, block).as_ref());
+        }
+
+        let c = Dense::rand(100, 3);
+        assert_eq!(a.matmul(&c), a.matmul_blocked(&c, 16));
+        assert_eq!(None, a.matmul_blocked(&b, 0));
+        assert_eq!(None, c.matmul_blocked(&a, 16));
+    }
+
+    #[test]
+    fn diagonal_k() {
+        let mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        assert_eq!(vec![1.0, 5.0, 9.0], mat.diagonal_k(0));
+        assert_eq!(vec![2.0, 6.0], mat.diagonal_k(1));
+        assert_eq!(vec![4.0, 8.0], mat.diagonal_k(-1));
+        assert_eq!(vec![7.0], mat.diagonal_k(-2));
+
+        assert!(mat.diagonal_k(3).is_empty());
+        assert!(mat.diagonal_k(-3).is_empty());
+        assert!(mat.diagonal_k(i64::MIN).is_empty());
+    }
+
+    #[test]
+    fn mul_vec() {
+        let mat = Dense::from_str("1 2; -3 0.5; 0 4").unwrap();
+        assert_eq!(Some(vec![0.0, -6.5, -4.0]), mat.mul_vec(&[2.0, -1.0]));
+        assert_eq!(None, mat.mul_vec(&[1.0, 2.0, 3.0]));
+        assert_eq!(None, mat.mul_vec(&[]));
+        assert_eq!(Some(vec![0.0, 0.0]), Dense::zeros(2, 0).mul_vec(&[]));
+    }
+
+    #[test]
+    fn set_diagonal_k() {
+        let mut mat = Dense::identity(3);
+        mat.set_diagonal_k(1, &[-1.0, -2.0]).unwrap();
+        mat.set_diagonal_k(-2, &[5.0]).unwrap();
+        assert_eq!(vec![-1.0, -2.0], mat.diagonal_k(1));
+        assert_eq!(Dense::from_str("1 -1 0; 0 1 -2; 5 0 1").unwrap(), mat);
+
+        assert!(mat.set_diagonal_k(1, &[1.0, 2.0, 3.0]).is_err());
+        assert!(mat.set_diagonal_k(3, &[1.0]).is_err());
+        assert!(mat.set_diagonal_k(3, &[]).is_ok());
+    }
+
+    #[test]
+    fn transpose_into() {
+        let a = Dense::<i32>::from_str("1 2 3; 4 5 6").unwrap();
+        let b = Dense::<i32>::from_str("7 8 9; 10 11 12").unwrap();
+        let mut out = Dense::<i32>::zeros(3, 2);
+
+        a.transpose_into(&mut out).unwrap();
+        assert_eq!(a.transpose(), out);
+
+        b.transpose_into(&mut out).unwrap();
+        assert_eq!(b.transpose(), out);
+
+        let mut wrong = Dense::<i32>::zeros(2, 3);
+        assert!(a.transpose_into(&mut wrong).is_err());
+    }
+
+    #[test]
+    fn matmul_into() {
+        let a = Dense::from_str("1 2 3; 4 5 6").unwrap();
+        let b = Dense::from_str("7 8; 9 10; 11 12").unwrap();
+        let c = Dense::from_str("-1 0; 2 0.5; 0 3").unwrap();
+        let mut out = Dense::zeros(2, 2);
+
+        a.matmul_into(&b, &mut out).unwrap();
+        assert_eq!(a.matmul(&b).unwrap(), out);
+
+        a.matmul_into(&c, &mut out).unwrap();
+        assert_eq!(a.matmul(&c).unwrap(), out);
+
+        let mut wrong = Dense::zeros(3, 3);
+        assert!(a.matmul_into(&b, &mut wrong).is_err());
+        assert!(a.matmul_into(&a, &mut out).is_err());
+    }
+
+    #[test]
+    fn scale_row_and_col() {
+        let mut mat = Dense::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+
+        mat.scale_row(1, 2.0).unwrap();
+        assert_eq!(Dense::from_str("1 2 3; 8 10 12; 7 8 9").unwrap(), mat);
+
+        mat.scale_col(2, 0.5).unwrap();
+        assert_eq!(Dense::from_str("1 2 1.5; 8 10 6; 7 8 4.5").unwrap(), mat);
+
+        assert!(mat.scale_row(3, 2.0).is_err());
+        assert!(mat.scale_col(3, 2.0).is_err());
+    }
+
+    #[test]
+    fn weighted_frobenius() {
+        let a = Dense::from_str("1 -2 3; 0 4 0.5").unwrap();
+        let ones = Dense::from_fn(2, 3, |_, _| 1.0);
+
+        let unweighted = a.frobenius_inner(&a).unwrap().sqrt() as f64;
+        let weighted = a.weighted_frobenius(&ones).unwrap();
+        assert!((unweighted - weighted).abs() < EPSILON as f64);
+
+        let mut negative = ones.clone();
+        negative[(1, 2)] = -1.0;
+        assert_eq!(None, a.weighted_frobenius(&negative));
+        assert_eq!(None, a.weighted_frobenius(&ones.transpose()));
     }
 }
@@ -1,5 +1,34 @@
+mod accumulator;
+mod iter;
 mod mat;
-mod parse;
+mod npy;
+pub(crate) mod parse;
+#[cfg(feature = "serde")]
+mod serialize;
 mod traits;
 
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+
+pub use accumulator::*;
+pub use iter::*;
 pub use mat::*;
+
+trait DenseImplTraits:
+    From<u8> + Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + PartialEq + Display
+{
+}
+
+impl DenseImplTraits for f64 {}
+impl DenseImplTraits for f32 {}
+
+impl DenseImplTraits for i128 {}
+impl DenseImplTraits for i64 {}
+impl DenseImplTraits for i32 {}
+impl DenseImplTraits for i16 {}
+
+impl DenseImplTraits for u128 {}
+impl DenseImplTraits for u64 {}
+impl DenseImplTraits for u32 {}
+impl DenseImplTraits for u16 {}
+impl DenseImplTraits for u8 {}
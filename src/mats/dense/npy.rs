@@ -0,0 +1,202 @@
+//! Parsing of the NumPy `.npy` binary format.
+//! See the [format specification](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html).
+use crate::Entry;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Reads the value of the given `key` in the header dictionary of a `.npy` file.
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+    let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+
+    let end = match rest.chars().next()? {
+        '(' => rest.find(')')? + 1,
+        '\'' => rest[1..].find('\'')? + 2,
+        _ => rest.find([',', '}'])?,
+    };
+
+    Some(rest[..end].trim())
+}
+
+/// Parses the given `.npy` buffer: `bytes` and returns the data
+/// and dimensions of the stored matrix.
+///
+/// # Errors
+/// 1. The buffer is not a valid `.npy` file.
+/// 1. The stored array is not a little-endian `f32` array.
+/// 1. The stored array is in Fortran order.
+/// 1. The stored array is not 2-dimensional.
+/// 1. The size of the stored array overflows `usize`.
+pub fn mat_from_npy(bytes: &[u8]) -> Result<(Vec<Entry>, usize, usize), &'static str> {
+    let rest = bytes
+        .strip_prefix(MAGIC)
+        .ok_or("Invalid npy magic string")?;
+
+    let (header_len, rest) = match rest {
+        [1, _, a, b, rest @ ..] => (u16::from_le_bytes([*a, *b]) as usize, rest),
+        [2 | 3, _, a, b, c, d, rest @ ..] => (u32::from_le_bytes([*a, *b, *c, *d]) as usize, rest),
+        _ => return Err("Unsupported npy version"),
+    };
+
+    if rest.len() < header_len {
+        return Err("Invalid npy header");
+    }
+
+    let (header, data) = rest.split_at(header_len);
+    let header = std::str::from_utf8(header).map_err(|_| "Invalid npy header")?;
+
+    let descr = header_value(header, "descr").ok_or("Invalid npy header")?;
+    if descr != "'<f4'" {
+        return Err("Unsupported npy dtype");
+    }
+
+    match header_value(header, "fortran_order") {
+        Some("False") => {}
+        Some("True") => return Err("Unsupported npy Fortran order"),
+        _ => return Err("Invalid npy header"),
+    }
+
+    let shape = header_value(header, "shape").ok_or("Invalid npy header")?;
+    let shape: Vec<usize> = shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse().map_err(|_| "Invalid npy shape"))
+        .collect::<Result<_, _>>()?;
+
+    let [n, m] = shape[..] else {
+        return Err("Unsupported npy shape");
+    };
+
+    let size = std::mem::size_of::<Entry>();
+    let len = n
+        .checked_mul(m)
+        .and_then(|nm| nm.checked_mul(size))
+        .ok_or("Invalid npy shape")?;
+
+    if data.len() != len {
+        return Err("Invalid npy data length");
+    }
+
+    let data = data
+        .chunks_exact(size)
+        .map(|b| Entry::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    Ok((data, n, m))
+}
+
+/// Writes the given matrix data and dimensions as a version 1.0 `.npy` buffer
+/// holding a little-endian `f32` array in C order.
+pub fn mat_to_npy(data: &[Entry], n: usize, m: usize) -> Vec<u8> {
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({n}, {m}), }}");
+
+    // The header is padded so that the data starts at a multiple of 64 bytes.
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.extend(std::iter::repeat_n(
+        ' ',
+        unpadded.next_multiple_of(64) - unpadded,
+    ));
+    header.push('\n');
+
+    let mut bytes =
+        Vec::with_capacity(MAGIC.len() + 4 + header.len() + std::mem::size_of_val(data));
+    bytes.extend(MAGIC);
+    bytes.extend([1, 0]);
+    bytes.extend((header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+    bytes.extend(data.iter().flat_map(|e| e.to_le_bytes()));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a version 1.0 `.npy` buffer with the given header dictionary and data.
+    fn npy(dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut header = dict.to_string();
+        while !(MAGIC.len() + 4 + header.len() + 1).is_multiple_of(64) {
+            header.push(' ');
+        }
+        header.push('\n');
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([1, 0]);
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn valid() {
+        let data: Vec<u8> = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]
+            .iter()
+            .flat_map(|e| e.to_le_bytes())
+            .collect();
+
+        let bytes = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }",
+            &data,
+        );
+        let (data, rows, cols) = mat_from_npy(&bytes).unwrap();
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], data);
+        assert_eq!(2, rows);
+        assert_eq!(3, cols);
+    }
+
+    #[test]
+    fn invalid() {
+        let data = [0; 16];
+
+        let bytes = npy(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 1), }",
+            &data,
+        );
+        assert!(mat_from_npy(&bytes).is_err());
+
+        let bytes = npy(
+            "{'descr': '<f4', 'fortran_order': True, 'shape': (2, 2), }",
+            &data,
+        );
+        assert!(mat_from_npy(&bytes).is_err());
+
+        let bytes = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (4,), }",
+            &data,
+        );
+        assert!(mat_from_npy(&bytes).is_err());
+
+        let bytes = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (3, 2), }",
+            &data,
+        );
+        assert!(mat_from_npy(&bytes).is_err());
+
+        assert!(mat_from_npy(b"NUMPY").is_err());
+    }
+
+    #[test]
+    fn oversized_shape() {
+        let bytes = npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (1099511627776, 1099511627776), }",
+            &[0; 16],
+        );
+        assert_eq!(Err("Invalid npy shape"), mat_from_npy(&bytes));
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = [1.5, -2.0, 0.0, 4.25, 1e-3, 6.0];
+        let bytes = mat_to_npy(&data, 3, 2);
+        assert_eq!(0, (bytes.len() - data.len() * 4) % 64);
+
+        let (res, rows, cols) = mat_from_npy(&bytes).unwrap();
+        assert_eq!(data.to_vec(), res);
+        assert_eq!(3, rows);
+        assert_eq!(2, cols);
+    }
+}
@@ -78,6 +78,42 @@ pub fn mat_from_str<T: FromStr>(
     Ok((data, rows, cols))
 }
 
+/// Parses a given string: `s` of comma separated values and returns
+/// the data and dimensions of the given matrix. Every line is a row
+/// and trailing empty lines are ignored.
+///
+/// # Errors
+/// 1. The string has no rows.
+/// 1. At least one of the given entries is not the same type as the given `T`.
+/// 1. The rows don't have the same number of entries.
+pub fn mat_from_csv<T: FromStr>(s: &str) -> Result<(Vec<T>, usize, usize), &'static str> {
+    let lines: Vec<&str> = s.trim_end_matches(['\n', '\r']).lines().collect();
+    if lines.is_empty() || lines == [""] {
+        return Err("Given string is empty");
+    }
+
+    let mut data = vec![];
+    let cols = lines[0].split(',').count();
+
+    for line in &lines {
+        let mut read = 0;
+        for num in line.split(',') {
+            data.push(
+                num.trim()
+                    .parse::<T>()
+                    .map_err(|_| "Invalid syntax in string")?,
+            );
+            read += 1;
+        }
+
+        if read != cols {
+            return Err("Invalid shape for matrix");
+        }
+    }
+
+    Ok((data, lines.len(), cols))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +150,22 @@ mod tests {
         assert!(mat_from_str::<f32>("1 2; b 4", ";").is_err());
         assert!(mat_from_str::<i64>("", ".").is_err());
     }
+
+    #[test]
+    fn csv() {
+        let (data, rows, cols) = mat_from_csv::<f32>("1, 2,3\n4,5 ,6\n\n").unwrap();
+        assert_eq!(vec![1., 2., 3., 4., 5., 6.], data);
+        assert_eq!(2, rows);
+        assert_eq!(3, cols);
+
+        let (data, rows, cols) = mat_from_csv::<i32>("1\r\n2\r\n").unwrap();
+        assert_eq!(vec![1, 2], data);
+        assert_eq!(2, rows);
+        assert_eq!(1, cols);
+
+        assert!(mat_from_csv::<i32>("1,2\n3").is_err());
+        assert!(mat_from_csv::<i32>("1,a").is_err());
+        assert!(mat_from_csv::<i32>("1,2\n\n3,4").is_err());
+        assert!(mat_from_csv::<i32>("\n").is_err());
+    }
 }
@@ -0,0 +1,36 @@
+//! `serde` support for `Dense`, serialized as its shape and row-major entries.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::mat::{Dense, DenseConstructors};
+use super::DenseImplTraits;
+
+#[derive(Serialize, Deserialize)]
+struct DenseRepr<T> {
+    shape: (usize, usize),
+    data: Vec<T>,
+}
+
+#[allow(private_bounds)]
+impl<T: DenseImplTraits + Serialize> Serialize for Dense<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (n, m) = self.shape();
+        let data = (0..n * m).map(|k| self[(k / m, k % m)]).collect::<Vec<T>>();
+        let shape = (n, m);
+        DenseRepr { shape, data }.serialize(serializer)
+    }
+}
+
+#[allow(private_bounds)]
+impl<'de, T: DenseImplTraits + Deserialize<'de>> Deserialize<'de> for Dense<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let DenseRepr {
+            shape: (n, m),
+            data,
+        } = DenseRepr::<T>::deserialize(deserializer)?;
+        if data.len() != n * m {
+            return Err(serde::de::Error::custom("Invalid shape for matrix"));
+        }
+
+        Ok(DenseConstructors::from_fn(n, m, |i, j| data[i * m + j]))
+    }
+}
@@ -1,11 +1,13 @@
 use std::fmt::{self, Display};
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
-use super::mat::Dense;
-use crate::Entry;
+use super::mat::{Dense, DenseConstructors};
+use super::DenseImplTraits;
+use crate::matrix::Matrix;
 
-impl Index<(usize, usize)> for Dense {
-    type Output = Entry;
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Index<(usize, usize)> for Dense<T> {
+    type Output = T;
     /// Returns the entry at `idx: (i, j)`.
     ///
     /// # Usage
@@ -22,7 +24,7 @@ impl Index<(usize, usize)> for Dense {
     ///
     /// ```should_panic
     /// use mat_lib::dense::*;
-    /// let mat = Dense::from_str("1 2; 3 4").unwrap();
+    /// let mat = Dense::<i32>::from_str("1 2; 3 4").unwrap();
     /// let panic = mat[(2, 2)];
     /// ```
     fn index(&self, idx: (usize, usize)) -> &Self::Output {
@@ -30,7 +32,8 @@ impl Index<(usize, usize)> for Dense {
     }
 }
 
-impl IndexMut<(usize, usize)> for Dense {
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> IndexMut<(usize, usize)> for Dense<T> {
     /// Returns a mutable reference to the entry at `idx: (i, j)`.
     ///
     /// # Usage
@@ -54,26 +57,108 @@ impl IndexMut<(usize, usize)> for Dense {
     }
 }
 
-impl Display for Dense {
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Display for Dense<T> {
+    /// Formats the matrix as `to_string_with(4, 0)`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (n, m) = self.shape();
-        let mut rows = Vec::with_capacity(n);
+        write!(f, "{}", self.to_string_with(4, 0))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Matrix<T> for Dense<T> {
+    fn zeros(n: usize, m: usize) -> Self {
+        DenseConstructors::zeros(n, m)
+    }
+
+    fn get(&self, idx: (usize, usize)) -> Option<&T> {
+        Dense::get(self, idx)
+    }
+
+    fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+        Dense::get_mut(self, idx)
+    }
+
+    fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
+        Dense::set(self, idx, val)
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        Dense::shape(self)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DenseImplTraits, const N: usize, const M: usize> From<[[T; M]; N]> for Dense<T> {
+    /// Creates an `N x M` `Dense` matrix from an array of rows.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let mat = Dense::from([[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(Dense::from_str("1 2; 3 4").unwrap(), mat);
+    /// ```
+    fn from(rows: [[T; M]; N]) -> Self {
+        DenseConstructors::from_fn(N, M, |i, j| rows[i][j])
+    }
+}
 
-        let len = 7;
-        for i in 0..n {
-            let mut row = String::with_capacity(len * m + 3);
-            row.push('[');
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Add for &Dense<T> {
+    type Output = Dense<T>;
+    /// Computes the element-wise addition between both matrices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both matrices don't have the same shape.
+    fn add(self, rhs: Self) -> Self::Output {
+        Dense::add(self, rhs).expect("Matrices must have the same shape to be added")
+    }
+}
 
-            for j in 0..m {
-                let num = self[(i, j)];
-                let fmt = format!(" {num:.4}");
-                row.push_str(&fmt[..len.min(fmt.len())]);
-            }
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Sub for &Dense<T> {
+    type Output = Dense<T>;
+    /// Computes the element-wise subtraction between both matrices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both matrices don't have the same shape.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Dense::sub(self, rhs).expect("Matrices must have the same shape to be subtracted")
+    }
+}
 
-            row.push_str(" ]");
-            rows.push(row);
-        }
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Mul for &Dense<T> {
+    type Output = Dense<T>;
+    /// Computes the matrix product between both matrices.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use mat_lib::dense::*;
+    /// let a = Dense::<i32>::from_str("1 2; 3 4").unwrap();
+    /// let b = Dense::<i32>::from_str("1; 1").unwrap();
+    /// assert_eq!(Dense::<i32>::from_str("3; 7").unwrap(), &a * &b);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns of `self` differs from the number of rows of `rhs`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.matmul(rhs)
+            .expect("Number of columns must match the number of rows of the right hand side")
+    }
+}
 
-        write!(f, "{}", rows.join("\n"))
+#[allow(private_bounds)]
+impl<T: DenseImplTraits> Mul<T> for &Dense<T> {
+    type Output = Dense<T>;
+    /// Computes the multiplication between the matrix and a scalar.
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut res = self.clone();
+        res.scalar_mul(rhs);
+        res
     }
 }
@@ -2,11 +2,16 @@
 //! It is often used when every entry in the matrix is 0 except for the diagonal.
 //! This implementation is more efficient than the dense matrix implementation for this use case.
 use super::DiagImplTraits;
+use crate::dense::Dense;
+use crate::mats::display::grid;
+use crate::sparse::{Sparse, SparseImplTraits};
+use crate::Entry;
+use rand::distributions::uniform::SampleUniform;
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 use std::mem;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Diag<T, const N: usize, const M: usize> {
     data: Vec<T>,
     // self.zero is used to return a reference to
@@ -43,6 +48,31 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
         Self { data, zero }
     }
 
+    /// Initializes a new `N x M` where the diagonal is filled with random values
+    /// sampled uniformly from the range `[low, high)`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<i32, 3, 3>::rand_range(-5, 5);
+    /// assert!((0..3).all(|i| (-5..5).contains(&mat[(i, i)])));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` is not less than `high`.
+    pub fn rand_range(low: T, high: T) -> Self
+    where
+        T: SampleUniform + PartialOrd,
+    {
+        let zero = T::from(0);
+        let mut rand_gen = rand::thread_rng();
+        let data = (0..N.min(M))
+            .map(|_| rand_gen.gen_range(low..high))
+            .collect();
+        Self { data, zero }
+    }
+
     /// Instanciates a new `Diag` type matrix with the given elements and shape.
     ///
     /// # Usage
@@ -95,11 +125,12 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
     /// assert_eq!(Some(&2.0), mat.get((1, 1)));
     /// assert!(mat.get((2, 0)).is_none());
     /// ```
-    #[allow(unused_variables)]
-    pub fn get(&self, idx @ (i, j): (usize, usize)) -> Option<&T> {
-        self.data
-            .get(i)
-            .map(|num| if i == j { num } else { &self.zero })
+    pub fn get(&self, (i, j): (usize, usize)) -> Option<&T> {
+        if i >= N || j >= M {
+            return None;
+        }
+
+        Some(if i == j { &self.data[i] } else { &self.zero })
     }
 
     /// Returns a mutable reference to the entry at the given `idx: (i, j)`.
@@ -117,9 +148,8 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
     /// assert_eq!(1.0, mat[(0, 0)]);
     /// assert_eq!(None, mat.get_mut((3, 2)));
     /// ```
-    #[allow(unused_variables)]
-    pub fn get_mut(&mut self, idx @ (i, j): (usize, usize)) -> Option<&mut T> {
-        (i == j).then(|| self.data.get_mut(i)).flatten()
+    pub fn get_mut(&mut self, (i, j): (usize, usize)) -> Option<&mut T> {
+        (i < N && j < M && i == j).then(|| &mut self.data[i])
     }
 
     /// Sets `val` to the given `idx: (i, j)` in the matrix
@@ -164,6 +194,22 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
         self.apply(|e| e * rhs)
     }
 
+    /// Adds the scalar `rhs` to every entry in the diagonal of the matrix in-place.
+    /// Entries outside the diagonal are left as zero.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mut mat = Diag::<f32, 2, 3>::from([1.0, 2.0]).unwrap();
+    /// mat.scalar_add(0.5);
+    ///
+    /// let res = Diag::<f32, 2, 3>::from([1.5, 2.5]).unwrap();
+    /// assert_eq!(res, mat);
+    /// ```
+    pub fn scalar_add(&mut self, rhs: T) -> &mut Self {
+        self.apply(|e| e + rhs)
+    }
+
     /// Applies the given function `f` to every entry in the matrix.
     ///
     /// # Usage
@@ -180,6 +226,119 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
         self
     }
 
+    /// Converts the matrix into a `Dense` matrix, placing the diagonal values
+    /// and filling the rest of the entries with zeros.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<f32, 2, 3>::from([1.0, 2.0]).unwrap();
+    ///
+    /// let res = Dense::from_str("1 0 0; 0 2 0").unwrap();
+    /// assert_eq!(res, mat.to_dense());
+    /// ```
+    pub fn to_dense(&self) -> Dense
+    where
+        T: Into<Entry>,
+    {
+        let mut res = Dense::zeros(N, M);
+        for (i, &val) in self.data.iter().enumerate() {
+            res[(i, i)] = val.into();
+        }
+
+        res
+    }
+
+    /// Converts the given matrix into a sparse matrix, only storing the
+    /// non-zero entries of the diagonal.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<i32, 2, 3>::from([4, 0]).unwrap();
+    /// let sparse = mat.to_sparse();
+    ///
+    /// assert_eq!(1, sparse.nnz());
+    /// assert_eq!(4, sparse[(0, 0)]);
+    /// ```
+    pub fn to_sparse(&self) -> Sparse<T, N, M>
+    where
+        T: SparseImplTraits,
+    {
+        let mut res = Sparse::zeros();
+        for (i, &val) in self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, &val)| val != self.zero)
+        {
+            res.set((i, i), val);
+        }
+
+        res
+    }
+
+    /// Returns the transpose of the given matrix. The diagonal of a
+    /// matrix and its transpose are the same, so only the shape changes.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<i32, 2, 4>::from([1, 2]).unwrap();
+    /// let t = mat.transpose();
+    ///
+    /// assert_eq!((4, 2), t.shape());
+    /// assert_eq!(2, t[(1, 1)]);
+    /// ```
+    pub fn transpose(&self) -> Diag<T, M, N> {
+        Diag {
+            data: self.data.clone(),
+            zero: self.zero,
+        }
+    }
+
+    /// Formats the matrix with `precision` decimals, see `display::grid` for the layout.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<f32, 2, 2>::from([1234.5678, -3.0]).unwrap();
+    /// assert_eq!("[ 1235    0 ]\n[    0   -3 ]", mat.to_string_with(0, 4));
+    /// ```
+    pub fn to_string_with(&self, precision: usize, width: usize) -> String {
+        grid(N, M, width, |i, j| format!("{:.precision$}", self[(i, j)]))
+    }
+
+    /// Computes the sum of the entries in the diagonal of the matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<i32, 3, 3>::from([1, 2, 3]).unwrap();
+    /// assert_eq!(6, mat.trace());
+    /// ```
+    pub fn trace(&self) -> T {
+        self.data.iter().fold(self.zero, |acc, &e| acc + e)
+    }
+
+    /// Returns a new matrix with the result of applying `f` to every entry
+    /// in the diagonal, which may have a different element type.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// let mat = Diag::<i32, 2, 2>::from([1, 3]).unwrap();
+    /// let res: Diag<f64, 2, 2> = mat.map(|&e| e as f64 / 2.0);
+    /// assert_eq!(Diag::from([0.5, 1.5]).unwrap(), res);
+    /// ```
+    pub fn map<U: DiagImplTraits, F: Fn(&T) -> U>(&self, f: F) -> Diag<U, N, M> {
+        Diag {
+            data: self.data.iter().map(f).collect(),
+            zero: U::from(0),
+        }
+    }
+
     /// Computes the determinant of the given matrix.
     ///
     /// # Example
@@ -212,6 +371,7 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> Diag<T, N, M> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::str::FromStr;
 
     #[test]
     fn ident() {
@@ -271,4 +431,117 @@ mod tests {
         mat.inv();
         assert_eq!(Diag::<_, 5, 5>::from([0.5, 1.0 / 3.0, 0.25]).unwrap(), mat);
     }
+
+    #[test]
+    fn to_dense() {
+        let mat = Diag::<u8, 3, 2>::from([4, 5]).unwrap();
+
+        let dense = mat.to_dense();
+        assert_eq!((3, 2), dense.shape());
+        assert_eq!(Dense::from_str("4 0; 0 5; 0 0").unwrap(), dense);
+    }
+
+    #[test]
+    fn transpose() {
+        let mat = Diag::<_, 2, 3>::from([1.0, 2.0]).unwrap();
+        let t: Diag<_, 3, 2> = mat.transpose();
+
+        assert_eq!((3, 2), t.shape());
+        assert_eq!(mat.data, t.data);
+        assert_eq!(1.0, t[(0, 0)]);
+        assert_eq!(2.0, t[(1, 1)]);
+        assert_eq!(0.0, t[(0, 1)]);
+    }
+
+    #[test]
+    fn from_str() {
+        let mat = Diag::<f32, 3, 4>::from_str("1 2.5 -3").unwrap();
+        assert_eq!(Diag::from([1.0, 2.5, -3.0]).unwrap(), mat);
+
+        let mat = Diag::<u8, 4, 4>::from_str("7").unwrap();
+        assert_eq!(vec![7, 0, 0, 0], mat.data);
+
+        assert!(Diag::<f32, 2, 4>::from_str("1 2 3").is_err());
+        assert!(Diag::<f32, 3, 3>::from_str("1; 2").is_err());
+        assert!(Diag::<u8, 3, 3>::from_str("1 -2").is_err());
+    }
+
+    #[test]
+    fn trace() {
+        let mat = Diag::<u32, 6, 6>::ident();
+        assert_eq!(6, mat.trace());
+
+        let mat = Diag::<_, 2, 5>::from([1.5, -0.5]).unwrap();
+        assert_eq!(1.0, mat.trace());
+    }
+
+    #[test]
+    fn map() {
+        let mat = Diag::<u8, 3, 4>::from([1, 2, 3]).unwrap();
+        let res: Diag<i64, 3, 4> = mat.map(|&x| -(x as i64));
+        assert_eq!(vec![-1, -2, -3], res.data);
+        assert_eq!(0, res[(0, 1)]);
+    }
+
+    #[test]
+    fn clone() {
+        let a = Diag::<f32, 2, 2>::from([1.0, 2.0]).unwrap();
+        let mut b = a.clone();
+        b.scalar_mul(2.0);
+
+        assert_eq!(vec![1.0, 2.0], a.data);
+        assert_eq!(vec![2.0, 4.0], b.data);
+    }
+
+    #[test]
+    fn rand_range() {
+        let mat = Diag::<f64, 20, 30>::rand_range(-2.0, 3.0);
+        assert!(mat.data.iter().all(|x| (-2.0..3.0).contains(x)));
+        assert_eq!(20, mat.data.len());
+
+        let mat = Diag::<u8, 50, 50>::rand_range(10, 12);
+        assert!(mat.data.iter().all(|x| (10..12).contains(x)));
+    }
+
+    #[test]
+    fn scalar_add() {
+        let mut mat = Diag::<i32, 3, 2>::from([1, -2]).unwrap();
+        mat.scalar_add(3).scalar_add(-1);
+        assert_eq!(vec![3, 0], mat.data);
+        assert_eq!(0, mat[(1, 0)]);
+    }
+
+    #[test]
+    fn get_bounds() {
+        let mut mat = Diag::<i32, 5, 2>::from([1, 2]).unwrap();
+        assert_eq!(None, mat.get((0, 7)));
+        assert_eq!(None, mat.get((1, 2)));
+        assert_eq!(None, mat.get((5, 0)));
+        assert_eq!(None, mat.get_mut((0, 7)));
+        assert_eq!(None, mat.get_mut((2, 2)));
+
+        // Rows past the diagonal are still in range.
+        assert_eq!(Some(&0), mat.get((4, 1)));
+        assert_eq!(Some(&2), mat.get((1, 1)));
+
+        let mut mat = Diag::<i32, 2, 5>::from([1, 2]).unwrap();
+        assert_eq!(None, mat.get((0, 5)));
+        assert_eq!(Some(&0), mat.get((1, 4)));
+        assert_eq!(None, mat.set((0, 5), 3));
+    }
+
+    #[test]
+    fn to_sparse() {
+        let mat = Diag::<i32, 4, 4>::from([1, 0, -3, 7]).unwrap();
+        let sparse = mat.to_sparse();
+
+        assert_eq!(3, sparse.nnz());
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(mat[(i, j)], sparse[(i, j)]);
+            }
+        }
+
+        assert_eq!(0, Diag::<i32, 4, 4>::zeros().to_sparse().nnz());
+    }
 }
@@ -1,4 +1,6 @@
 mod mat;
+#[cfg(feature = "serde")]
+mod serialize;
 mod traits;
 
 use std::fmt::Display;
@@ -6,7 +8,7 @@ use std::ops::{Add, Div, Mul};
 
 pub use mat::*;
 
-trait DiagImplTraits:
+pub(crate) trait DiagImplTraits:
     Add<Output = Self> + Copy + Mul<Output = Self> + PartialEq + Div<Output = Self> + Display + From<u8>
 {
 }
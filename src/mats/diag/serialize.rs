@@ -0,0 +1,39 @@
+//! `serde` support for `Diag`, serialized as its shape and diagonal entries.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::mat::Diag;
+use super::DiagImplTraits;
+
+#[derive(Serialize, Deserialize)]
+struct DiagRepr<T> {
+    shape: (usize, usize),
+    diagonal: Vec<T>,
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits + Serialize, const N: usize, const M: usize> Serialize for Diag<T, N, M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let diagonal = (0..N.min(M)).map(|i| self[(i, i)]).collect();
+        let shape = (N, M);
+        DiagRepr { shape, diagonal }.serialize(serializer)
+    }
+}
+
+#[allow(private_bounds)]
+impl<'de, T: DiagImplTraits + Deserialize<'de>, const N: usize, const M: usize> Deserialize<'de>
+    for Diag<T, N, M>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = DiagRepr::<T>::deserialize(deserializer)?;
+        if repr.shape != (N, M) || repr.diagonal.len() != N.min(M) {
+            return Err(serde::de::Error::custom("Invalid shape for matrix"));
+        }
+
+        let mut mat = Self::zeros();
+        for (i, val) in repr.diagonal.into_iter().enumerate() {
+            mat.set((i, i), val);
+        }
+
+        Ok(mat)
+    }
+}
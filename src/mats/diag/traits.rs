@@ -1,8 +1,11 @@
 use std::fmt::{self, Display};
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
 use super::mat::Diag;
 use super::DiagImplTraits;
+use crate::dense::parse::mat_from_str;
+use crate::matrix::Matrix;
 
 impl<T: DiagImplTraits, const N: usize, const M: usize> Index<(usize, usize)> for Diag<T, N, M> {
     type Output = T;
@@ -18,25 +21,73 @@ impl<T: DiagImplTraits, const N: usize, const M: usize> IndexMut<(usize, usize)>
 }
 
 impl<T: DiagImplTraits, const N: usize, const M: usize> Display for Diag<T, N, M> {
+    /// Formats the matrix as `to_string_with(4, 0)`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (n, m) = self.shape();
-        let mut rows = Vec::with_capacity(n);
-
-        let len = 7;
-        for i in 0..n {
-            let mut row = String::with_capacity(len * m + 3);
-            row.push('[');
-
-            for j in 0..m {
-                let num = self[(i, j)];
-                let fmt = format!(" {num:.4}");
-                row.push_str(&fmt[..len.min(fmt.len())]);
-            }
-
-            row.push_str(" ]");
-            rows.push(row);
+        write!(f, "{}", self.to_string_with(4, 0))
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits, const N: usize, const M: usize> Matrix<T> for Diag<T, N, M> {
+    /// The shape of a `Diag` is fixed by `N` and `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(n, m)` differs from `(N, M)`.
+    fn zeros(n: usize, m: usize) -> Self {
+        assert_eq!((N, M), (n, m), "Shape does not match the matrix dimensions");
+        Diag::zeros()
+    }
+
+    fn get(&self, idx: (usize, usize)) -> Option<&T> {
+        Diag::get(self, idx)
+    }
+
+    fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+        Diag::get_mut(self, idx)
+    }
+
+    fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
+        Diag::set(self, idx, val)
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        Diag::shape(self)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: DiagImplTraits + FromStr, const N: usize, const M: usize> FromStr for Diag<T, N, M> {
+    type Err = &'static str;
+    /// Create a `Diag` matrix from a string of whitespace separated diagonal values.
+    /// If less than `MIN(N, M)` values are given, the rest of the diagonal is filled with `0`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::diag::Diag;
+    /// use std::str::FromStr;
+    ///
+    /// let mat = Diag::<i32, 3, 3>::from_str("1 2").unwrap();
+    /// assert_eq!(Diag::from([1, 2, 0]).unwrap(), mat);
+    ///
+    /// // Too many values.
+    /// assert!(Diag::<i32, 2, 3>::from_str("1 2 3").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (data, rows, _) = mat_from_str::<T>(s, ";")?;
+        if rows != 1 {
+            return Err("Invalid shape for matrix");
+        }
+
+        if data.len() > N.min(M) {
+            return Err("Invalid quantity of elements");
+        }
+
+        let mut mat = Self::zeros();
+        for (i, val) in data.into_iter().enumerate() {
+            mat.set((i, i), val);
         }
 
-        write!(f, "{}", rows.join("\n"))
+        Ok(mat)
     }
 }
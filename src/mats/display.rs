@@ -0,0 +1,39 @@
+/// Builds the aligned grid representation of an `n x m` matrix, shared by the
+/// `to_string_with` and `Display` implementations of every matrix type.
+///
+/// Every row is wrapped in brackets and every entry is formatted by `entry`, which
+/// the matrix types do with a fixed number of decimals. Entries are right aligned to
+/// a common width of at least `width` characters, growing to fit the widest entry so
+/// that numbers are never clipped.
+pub(crate) fn grid<F: Fn(usize, usize) -> String>(
+    n: usize,
+    m: usize,
+    width: usize,
+    entry: F,
+) -> String {
+    let entries: Vec<String> = (0..n)
+        .flat_map(|i| (0..m).map(move |j| (i, j)))
+        .map(|(i, j)| entry(i, j))
+        .collect();
+
+    let width = entries
+        .iter()
+        .map(|e| e.chars().count())
+        .fold(width, usize::max);
+
+    let rows: Vec<String> = (0..n)
+        .map(|i| {
+            let mut row = String::with_capacity((width + 1) * m + 3);
+            row.push('[');
+
+            for e in &entries[i * m..(i + 1) * m] {
+                row.push_str(&format!(" {e:>width$}"));
+            }
+
+            row.push_str(" ]");
+            row
+        })
+        .collect();
+
+    rows.join("\n")
+}
@@ -1,17 +1,151 @@
-use crate::Entry;
 use std::ops::*;
 
-pub trait Matrix: Index<(usize, usize)> + IndexMut<(usize, usize)> {
+/// Common interface shared by every matrix type in the crate,
+/// generic over the type `T` of its entries.
+///
+/// The arithmetic methods work through `zeros`, `set` and indexing, so they
+/// accept any other `Matrix<T>` as `rhs`. Only the non-zero entries of the
+/// result are written.
+///
+/// # Panics
+///
+/// The arithmetic methods panic if the shapes of the operands don't match, or
+/// if the result can't be stored in `Self`, e.g. a non-diagonal sum for a `Diag`
+/// or a non-square transpose for a type whose shape is fixed at compile time.
+pub trait Matrix<T>: Index<(usize, usize), Output = T> + IndexMut<(usize, usize)> {
+    /// Initializes a new `n x m` matrix filled with zeros.
     fn zeros(n: usize, m: usize) -> Self;
 
-    fn get(&self, idx: (usize, usize)) -> &Entry;
-    fn get_mut(&mut self, idx: (usize, usize)) -> &mut Entry;
-    fn set(&mut self, idx: (usize, usize), val: Entry) -> Option<Entry>;
+    fn get(&self, idx: (usize, usize)) -> Option<&T>;
+    fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T>;
+    fn set(&mut self, idx: (usize, usize), val: T) -> Option<T>;
     fn shape(&self) -> (usize, usize);
 
-    fn add<M: Matrix>(&mut self, rhs: M) -> Self;
-    fn mul<M: Matrix>(&mut self, rhs: M) -> Self;
-    fn sub<M: Matrix>(&mut self, rhs: M) -> Self;
+    /// Returns the entry-wise sum between the given matrix and `rhs`.
+    fn add<M: Matrix<T>>(&mut self, rhs: M) -> Self
+    where
+        Self: Sized,
+        T: Copy + PartialEq + From<u8> + Add<Output = T>,
+    {
+        assert_eq!(self.shape(), rhs.shape(), "Invalid shape for matrix");
+        let (n, m) = self.shape();
+        from_fn(n, m, |i, j| self[(i, j)] + rhs[(i, j)])
+    }
 
-    fn t(&self) -> Self;
+    /// Returns the matrix product between the given matrix and `rhs`.
+    fn mul<M: Matrix<T>>(&mut self, rhs: M) -> Self
+    where
+        Self: Sized,
+        T: Copy + PartialEq + From<u8> + Add<Output = T> + Mul<Output = T>,
+    {
+        let (n, m) = self.shape();
+        let (p, q) = rhs.shape();
+        assert_eq!(m, p, "Invalid shape for matrix");
+
+        from_fn(n, q, |i, j| {
+            (0..m).fold(T::from(0), |acc, k| acc + self[(i, k)] * rhs[(k, j)])
+        })
+    }
+
+    /// Returns the entry-wise difference between the given matrix and `rhs`.
+    fn sub<M: Matrix<T>>(&mut self, rhs: M) -> Self
+    where
+        Self: Sized,
+        T: Copy + PartialEq + From<u8> + Sub<Output = T>,
+    {
+        assert_eq!(self.shape(), rhs.shape(), "Invalid shape for matrix");
+        let (n, m) = self.shape();
+        from_fn(n, m, |i, j| self[(i, j)] - rhs[(i, j)])
+    }
+
+    /// Returns the transpose of the given matrix.
+    fn t(&self) -> Self
+    where
+        Self: Sized,
+        T: Copy + PartialEq + From<u8>,
+    {
+        let (n, m) = self.shape();
+        from_fn(m, n, |i, j| self[(j, i)])
+    }
+}
+
+/// Builds an `n x m` matrix of type `Mat` from `f`, only writing the non-zero entries.
+fn from_fn<T, Mat, F>(n: usize, m: usize, f: F) -> Mat
+where
+    T: PartialEq + From<u8>,
+    Mat: Matrix<T>,
+    F: Fn(usize, usize) -> T,
+{
+    let zero = T::from(0);
+    let mut res = Mat::zeros(n, m);
+    for i in 0..n {
+        for j in 0..m {
+            let val = f(i, j);
+            if val != zero {
+                res.set((i, j), val)
+                    .expect("Result can't be stored in the matrix type");
+            }
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dense::Dense;
+    use crate::diag::Diag;
+    use crate::sparse::Sparse;
+
+    fn first_entry<T: Copy, Mat: Matrix<T>>(val: T) -> T {
+        let (n, m) = (2, 2);
+        let mut mat = Mat::zeros(n, m);
+        mat.set((0, 0), val);
+        assert_eq!((n, m), mat.shape());
+        mat[(0, 0)]
+    }
+
+    #[test]
+    fn generic_over_entry() {
+        assert_eq!(1.5, first_entry::<f32, Dense>(1.5));
+        assert_eq!(7, first_entry::<i32, Sparse<i32, 2, 2>>(7));
+        assert_eq!(3, first_entry::<u8, Diag<u8, 2, 2>>(3));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a = Dense::from([[1.0, 2.0], [3.0, 4.0]]);
+        let b = Dense::from([[0.0, 1.0], [1.0, 0.0]]);
+
+        assert_eq!(
+            Dense::from([[1.0, 3.0], [4.0, 4.0]]),
+            Matrix::add(&mut a, b.clone())
+        );
+        assert_eq!(
+            Dense::from([[1.0, 1.0], [2.0, 4.0]]),
+            Matrix::sub(&mut a, b.clone())
+        );
+        assert_eq!(
+            Dense::from([[2.0, 1.0], [4.0, 3.0]]),
+            Matrix::mul(&mut a, b)
+        );
+        assert_eq!(a.transpose(), Matrix::t(&a));
+
+        let mut s: Sparse<i32, 2, 2> = [((0, 1), 5)].into_iter().collect();
+        let d = Diag::<i32, 2, 2>::from([1, 2]).unwrap();
+        let sum = Matrix::add(&mut s, d.clone());
+        assert_eq!(3, sum.nnz());
+        assert_eq!(2, sum[(1, 1)]);
+        assert_eq!(10, Matrix::mul(&mut s, d)[(0, 1)]);
+        assert_eq!(5, Matrix::t(&s)[(1, 0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn arithmetic_not_representable() {
+        let mut d = Diag::<i32, 2, 2>::ident();
+        let s: Sparse<i32, 2, 2> = [((0, 1), 5)].into_iter().collect();
+        Matrix::add(&mut d, s);
+    }
 }
@@ -1,4 +1,6 @@
+pub mod banded;
 pub mod dense;
 pub mod diag;
+mod display;
 pub mod matrix;
 pub mod sparse;
@@ -0,0 +1,139 @@
+//! Compressed sparse row (CSR) storage for `Sparse` matrices, where the stored
+//! entries of every row are laid out contiguously for fast row-wise traversal.
+use std::ops::Add;
+
+use super::mat::Sparse;
+use super::SparseImplTraits;
+
+/// A read-only `N x M` matrix in compressed sparse row format. The entries of row `i`
+/// are `values[row_ptr[i]..row_ptr[i + 1]]` and their columns are stored at the same
+/// positions of `col_indices`.
+///
+/// # Usage
+/// ```
+/// use mat_lib::sparse::Sparse;
+/// let mat: Sparse<i32, 2, 3> = [((0, 0), 1), ((0, 2), 2), ((1, 1), 3)].into_iter().collect();
+/// let csr = mat.to_csr();
+/// assert_eq!(&[1, 2, 3], csr.values());
+/// assert_eq!(&[0, 2, 1], csr.col_indices());
+/// assert_eq!(&[0, 2, 3], csr.row_ptr());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseCsr<T, const N: usize, const M: usize> {
+    values: Vec<T>,
+    col_indices: Vec<usize>,
+    row_ptr: Vec<usize>,
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
+    /// Converts the matrix into the compressed sparse row format.
+    pub fn to_csr(&self) -> SparseCsr<T, N, M> {
+        let mut values = Vec::with_capacity(self.nnz());
+        let mut col_indices = Vec::with_capacity(self.nnz());
+        let mut row_ptr = vec![0; N + 1];
+
+        // Entries are iterated in row-major order.
+        for (&(i, j), &val) in self.iter() {
+            values.push(val);
+            col_indices.push(j);
+            row_ptr[i + 1] += 1;
+        }
+
+        for i in 0..N {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        SparseCsr {
+            values,
+            col_indices,
+            row_ptr,
+        }
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits, const N: usize, const M: usize> SparseCsr<T, N, M> {
+    /// Returns the stored entries in row-major order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the column of every stored entry.
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    /// Returns the `N + 1` offsets into `values` where every row starts.
+    pub fn row_ptr(&self) -> &[usize] {
+        &self.row_ptr
+    }
+
+    /// Returns the shape of the matrix in the format `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (N, M)
+    }
+
+    /// Computes the matrix-vector product `A·x`, returning a vector of len `N`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<i32, 2, 3> = [((0, 0), 1), ((0, 2), 2), ((1, 1), 3)].into_iter().collect();
+    /// assert_eq!(vec![7, 6], mat.to_csr().mul_vec(&[1, 2, 3]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the len of `x` differs from `M`.
+    pub fn mul_vec(&self, x: &[T]) -> Vec<T>
+    where
+        T: Add<Output = T>,
+    {
+        assert_eq!(M, x.len(), "Invalid quantity of elements");
+
+        self.row_ptr
+            .windows(2)
+            .map(|w| {
+                (w[0]..w[1]).fold(T::from(0), |acc, k| {
+                    acc + self.values[k] * x[self.col_indices[k]]
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_vec() {
+        let mat: Sparse<i64, 4, 3> = [
+            ((0, 0), 2),
+            ((0, 2), -1),
+            ((2, 1), 5),
+            ((3, 0), 1),
+            ((3, 1), 1),
+            ((3, 2), 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let x = [3, -2, 4];
+        let col: Sparse<i64, 3, 1> = x.iter().enumerate().map(|(i, &v)| ((i, 0), v)).collect();
+        let expected = mat.matmul(&col);
+
+        let res = mat.to_csr().mul_vec(&x);
+        assert_eq!(vec![2, 0, -10, 5], res);
+        assert!((0..4).all(|i| expected[(i, 0)] == res[i]));
+    }
+
+    #[test]
+    fn to_csr() {
+        let csr = Sparse::<f32, 3, 3>::zeros().to_csr();
+        assert!(csr.values().is_empty());
+        assert_eq!(&[0, 0, 0, 0], csr.row_ptr());
+        assert_eq!((3, 3), csr.shape());
+    }
+}
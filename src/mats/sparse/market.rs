@@ -0,0 +1,221 @@
+//! Reading and writing of `Sparse` matrices in the coordinate
+//! [Matrix Market](https://math.nist.gov/MatrixMarket/formats.html) format.
+use std::str::FromStr;
+
+use super::mat::Sparse;
+use super::SparseImplTraits;
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits + FromStr, const N: usize, const M: usize> Sparse<T, N, M> {
+    /// Creates a `Sparse` matrix from a string in the coordinate Matrix Market format.
+    ///
+    /// Both `general` and `symmetric` matrices are supported. Entries are
+    /// parsed as `T`, so the `real` and `integer` fields are accepted as long
+    /// as every value is a valid `T`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let s = "%%MatrixMarket matrix coordinate real general
+    /// % A comment
+    /// 2 3 2
+    /// 1 1 1.5
+    /// 2 3 -2";
+    ///
+    /// let mat = Sparse::<f32, 2, 3>::from_matrix_market(s).unwrap();
+    /// assert_eq!(1.5, mat[(0, 0)]);
+    /// assert_eq!(-2.0, mat[(1, 2)]);
+    /// ```
+    ///
+    /// # Errors
+    /// 1. The header is missing or describes an unsupported format.
+    /// 1. The header is `symmetric` but the matrix is not square.
+    /// 1. The declared dimensions differ from `(N, M)`.
+    /// 1. An entry is malformed or out of range.
+    /// 1. The number of entries differs from the declared one.
+    pub fn from_matrix_market(s: &str) -> Result<Self, &'static str> {
+        let mut lines = s.lines().map(str::trim);
+
+        let header = lines.next().ok_or("Given string is empty")?;
+        let header: Vec<_> = header.split_whitespace().map(str::to_lowercase).collect();
+
+        let [banner, object, format, field, symmetry] = header.as_slice() else {
+            return Err("Invalid Matrix Market header");
+        };
+
+        if banner != "%%matrixmarket" || object != "matrix" || format != "coordinate" {
+            return Err("Invalid Matrix Market header");
+        }
+
+        if field != "real" && field != "integer" && field != "double" {
+            return Err("Unsupported Matrix Market field");
+        }
+
+        let symmetric = match symmetry.as_str() {
+            "general" => false,
+            "symmetric" if N == M => true,
+            "symmetric" => return Err("Symmetric matrix must be square"),
+            _ => return Err("Unsupported Matrix Market symmetry"),
+        };
+
+        let mut lines = lines.filter(|line| !line.is_empty() && !line.starts_with('%'));
+
+        let size = lines.next().ok_or("Missing Matrix Market size line")?;
+        let size: Vec<usize> = size
+            .split_whitespace()
+            .map(|num| num.parse().map_err(|_| "Invalid Matrix Market size line"))
+            .collect::<Result<_, _>>()?;
+
+        let [rows, cols, nnz] = size.as_slice() else {
+            return Err("Invalid Matrix Market size line");
+        };
+
+        if (*rows, *cols) != (N, M) {
+            return Err("Invalid shape for matrix");
+        }
+
+        let mut mat = Self::zeros();
+        let mut read = 0;
+
+        for line in lines {
+            let mut entry = line.split_whitespace();
+            let (Some(i), Some(j), Some(val), None) =
+                (entry.next(), entry.next(), entry.next(), entry.next())
+            else {
+                return Err("Invalid Matrix Market entry");
+            };
+
+            let i: usize = i.parse().map_err(|_| "Invalid Matrix Market entry")?;
+            let j: usize = j.parse().map_err(|_| "Invalid Matrix Market entry")?;
+            let val: T = val.parse().map_err(|_| "Invalid syntax in string")?;
+
+            if i == 0 || j == 0 || i > N || j > M {
+                return Err("Matrix Market entry out of range");
+            }
+
+            mat.set((i - 1, j - 1), val);
+            if symmetric && mat.set((j - 1, i - 1), val).is_none() {
+                return Err("Matrix Market entry out of range");
+            }
+
+            read += 1;
+        }
+
+        if read != *nnz {
+            return Err("Invalid quantity of entries");
+        }
+
+        Ok(mat)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
+    /// Writes the matrix as a string in the coordinate Matrix Market format,
+    /// emitting only the stored entries with `1`-based indices. The field is
+    /// `integer` for integer types and `real` otherwise.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<f32, 2, 3> = [((0, 0), 1.5), ((1, 2), -2.0)].into_iter().collect();
+    ///
+    /// let s = "%%MatrixMarket matrix coordinate real general\n2 3 2\n1 1 1.5\n2 3 -2\n";
+    /// assert_eq!(s, mat.to_matrix_market());
+    /// ```
+    pub fn to_matrix_market(&self) -> String {
+        let field = if T::INTEGER { "integer" } else { "real" };
+        let mut s = format!("%%MatrixMarket matrix coordinate {field} general\n");
+        s.push_str(&format!("{N} {M} {}\n", self.nnz()));
+
+        for (&(i, j), val) in self.iter() {
+            s.push_str(&format!("{} {} {val}\n", i + 1, j + 1));
+        }
+
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_matrix_market() {
+        let s = "%%MatrixMarket matrix coordinate real general
+            %=================
+            % A 4x5 matrix
+            %=================
+            4 5 4
+            1 1 1.0
+            2 3 -2.5
+            4 2 3.25
+            4 5 4e2
+        ";
+
+        let mat = Sparse::<f32, 4, 5>::from_matrix_market(s).unwrap();
+        assert_eq!(4, mat.nnz());
+        assert_eq!(1.0, mat[(0, 0)]);
+        assert_eq!(-2.5, mat[(1, 2)]);
+        assert_eq!(3.25, mat[(3, 1)]);
+        assert_eq!(400.0, mat[(3, 4)]);
+    }
+
+    #[test]
+    fn from_matrix_market_symmetric() {
+        let s = "%%MatrixMarket matrix coordinate integer symmetric
+            3 3 2
+            1 1 4
+            3 1 7";
+
+        let mat = Sparse::<i32, 3, 3>::from_matrix_market(s).unwrap();
+        assert_eq!(3, mat.nnz());
+        assert_eq!(7, mat[(2, 0)]);
+        assert_eq!(7, mat[(0, 2)]);
+    }
+
+    #[test]
+    fn from_matrix_market_invalid() {
+        let header = "%%MatrixMarket matrix coordinate real general";
+
+        // Dimensions don't match.
+        let s = format!("{header}\n3 3 1\n1 1 1.0");
+        assert!(Sparse::<f32, 2, 2>::from_matrix_market(&s).is_err());
+
+        // Entry out of range.
+        let s = format!("{header}\n2 2 1\n3 1 1.0");
+        assert!(Sparse::<f32, 2, 2>::from_matrix_market(&s).is_err());
+
+        // Missing entries.
+        let s = format!("{header}\n2 2 2\n1 1 1.0");
+        assert!(Sparse::<f32, 2, 2>::from_matrix_market(&s).is_err());
+
+        // Unsupported format.
+        let s = "%%MatrixMarket matrix array real general\n2 2\n1.0\n2.0\n3.0\n4.0";
+        assert!(Sparse::<f32, 2, 2>::from_matrix_market(s).is_err());
+
+        assert!(Sparse::<f32, 2, 2>::from_matrix_market("").is_err());
+
+        // Symmetric but not square.
+        let s = "%%MatrixMarket matrix coordinate real symmetric\n2 3 1\n1 3 1.0";
+        assert!(Sparse::<f32, 2, 3>::from_matrix_market(s).is_err());
+    }
+
+    #[test]
+    fn to_matrix_market() {
+        let mat: Sparse<f64, 5, 4> = [((0, 3), 0.125), ((2, 2), -7.0), ((4, 0), 1e10)]
+            .into_iter()
+            .collect();
+
+        let s = mat.to_matrix_market();
+        assert_eq!(mat, Sparse::from_matrix_market(&s).unwrap());
+
+        let empty = Sparse::<i32, 2, 2>::zeros();
+        let s = empty.to_matrix_market();
+        assert_eq!(empty, Sparse::from_matrix_market(&s).unwrap());
+
+        let mat: Sparse<u8, 2, 2> = [((1, 0), 3)].into_iter().collect();
+        let s = "%%MatrixMarket matrix coordinate integer general\n2 2 1\n2 1 3\n";
+        assert_eq!(s, mat.to_matrix_market());
+    }
+}
@@ -1,7 +1,12 @@
-use super::SparseImplTraits;
-use std::collections::BTreeMap;
+use super::{Float, Signed, SparseImplTraits, Sqrt};
+use crate::dense::Dense;
+use crate::mats::display::grid;
+use crate::Entry;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Add, Deref, DerefMut, Div, Sub};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Sparse<T, const N: usize, const M: usize> {
     data: BTreeMap<(usize, usize), T>,
     // self.zero is used to return a reference to
@@ -22,6 +27,16 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
         i < N && j < M
     }
 
+    /// Materializes the `i`-th row of the matrix from the stored entries.
+    fn row(&self, i: usize) -> Vec<T> {
+        let mut row = vec![T::from(0); M];
+        for (&(_, j), &val) in self.data.range((i, 0)..(i + 1, 0)) {
+            row[j] = val;
+        }
+
+        row
+    }
+
     /// Returns a reference to the entry at the given `idx: (i, j)`.
     /// If the given index is out of bounds returns the `None` variant.
     ///
@@ -42,34 +57,54 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
             return None;
         }
 
-        self.data.get(&idx).or_else(|| Some(&self.zero))
+        self.data.get(&idx).or(Some(&self.zero))
     }
 
-    /// Returns a mutable reference to the entry at the given `idx: (i, j)`.
+    /// Returns a mutable handle to the entry at the given `idx: (i, j)`.
     /// If the given index is out of bounds returns the `None` variant.
     ///
+    /// The entry is written back when the handle is dropped, and it is only
+    /// stored if its final value is not `0`.
+    ///
     /// # Usage
     /// ```
     /// use mat_lib::sparse::Sparse;
     /// let mut mat = Sparse::<f32, 3, 2>::zeros();
     ///
-    /// if let Some(num) = mat.get_mut((0, 0)) {
+    /// if let Some(mut num) = mat.get_mut((0, 0)) {
     ///     *num = 1.0;
     /// }
     ///
+    /// let _ = mat.get_mut((1, 1));
+    ///
     /// assert_eq!(1.0, mat[(0, 0)]);
-    /// assert_eq!(None, mat.get_mut((3, 2)));
+    /// assert_eq!(1, mat.nnz());
+    /// assert!(mat.get_mut((3, 2)).is_none());
     /// ```
-    pub fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+    pub fn get_mut(&mut self, idx: (usize, usize)) -> Option<EntryMut<'_, T>> {
+        if !self.is_in_range(idx) {
+            return None;
+        }
+
+        let val = self.data.get(&idx).copied().unwrap_or(self.zero);
+        let data = &mut self.data;
+        Some(EntryMut { data, idx, val })
+    }
+
+    /// Returns a mutable reference to the entry at the given `idx: (i, j)`,
+    /// storing a `0` if it wasn't already present. Used where a plain
+    /// reference is required, such as `IndexMut`.
+    pub(super) fn entry_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
         if !self.is_in_range(idx) {
             return None;
         }
 
-        Some(self.data.entry(idx).or_insert(T::from(0)))
+        Some(self.data.entry(idx).or_insert(self.zero))
     }
 
     /// Sets `val` to the given `idx: (i, j)` in the matrix
-    /// and returns the previous value.
+    /// and returns the previous value. Setting an entry to `0`
+    /// removes it from the stored entries.
     ///
     /// # Usage
     /// ```
@@ -89,7 +124,13 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
             return None;
         }
 
-        self.data.insert(idx, val).or_else(|| Some(T::from(0)))
+        let prev = if val == self.zero {
+            self.data.remove(&idx)
+        } else {
+            self.data.insert(idx, val)
+        };
+
+        prev.or(Some(self.zero))
     }
 
     /// Returns the shape of the matrix in the format `(rows, cols)`.
@@ -101,6 +142,59 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
         N == M
     }
 
+    /// Returns the number of entries stored in the matrix.
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns an iterator over the stored entries of the matrix
+    /// in `(row, col)` order, skipping the implicit zeros.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<i32, 3, 3> = (0..3).map(|i| ((i, 2 - i), 1)).collect();
+    ///
+    /// for (&(i, j), &val) in mat.iter() {
+    ///     assert_eq!(2, i + j);
+    ///     assert_eq!(1, val);
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&(usize, usize), &T)> {
+        self.data.iter()
+    }
+
+    /// Returns an iterator over the indices of the stored entries of the matrix
+    /// in `(row, col)` order.
+    pub fn indices(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.data.keys().copied()
+    }
+
+    /// Returns the ratio between the stored entries and the size of the matrix.
+    pub fn density(&self) -> f64 {
+        self.nnz() as f64 / (N * M) as f64
+    }
+
+    /// Removes every stored entry equal to `0` so that `nnz` reflects
+    /// the true quantity of non-zero entries. Entries written through
+    /// `IndexMut` or `apply` may leave zeros behind.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mut mat = Sparse::<i32, 3, 3>::zeros();
+    /// mat[(0, 0)] = 0;
+    /// assert_eq!(1, mat.nnz());
+    ///
+    /// mat.prune();
+    /// assert_eq!(0, mat.nnz());
+    /// ```
+    pub fn prune(&mut self) -> &mut Self {
+        let zero = self.zero;
+        self.data.retain(|_, e| *e != zero);
+        self
+    }
+
     /// Computes the multiplication of the given matrix and a scalar `rhs` in-place.
     ///
     /// # Usage
@@ -137,27 +231,522 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> Sparse<T, N, M> {
         self
     }
 
+    /// Applies the given function `f` to every entry in the matrix,
+    /// passing the index `(i, j)` of the entry alongside its value.
+    ///
+    /// # Precaution
+    ///
+    /// This method will only affect entries that are already initialized.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    ///
+    /// let mut mat: Sparse<i32, 2, 2> = [((0, 1), 2), ((1, 0), 3)].into_iter().collect();
+    /// mat.apply_indexed(|(i, j), v| if i > j { 0 } else { v });
+    ///
+    /// let res: Sparse<i32, 2, 2> = [((0, 1), 2), ((1, 0), 0)].into_iter().collect();
+    /// assert_eq!(res, *mat.prune());
+    /// ```
+    pub fn apply_indexed<F: Fn((usize, usize), T) -> T>(&mut self, f: F) -> &mut Self {
+        self.data.iter_mut().for_each(|(&idx, e)| *e = f(idx, *e));
+        self
+    }
+
+    /// Computes the matrix product between the given matrix and `rhs`.
+    ///
+    /// Only the stored entries of both operands are visited, so the cost depends
+    /// on the quantity of non-zero entries rather than on `N * M * P`.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let a: Sparse<i32, 2, 3> = [((0, 1), 2), ((1, 2), 3)].into_iter().collect();
+    /// let b: Sparse<i32, 3, 2> = [((1, 0), 4), ((2, 1), 5)].into_iter().collect();
+    ///
+    /// let res: Sparse<i32, 2, 2> = [((0, 0), 8), ((1, 1), 15)].into_iter().collect();
+    /// assert_eq!(res, a.matmul(&b));
+    /// ```
+    pub fn matmul<const P: usize>(&self, rhs: &Sparse<T, M, P>) -> Sparse<T, N, P>
+    where
+        T: Add<Output = T>,
+    {
+        let mut acc = BTreeMap::new();
+
+        for (&(i, k), &a) in self.data.iter() {
+            for (&(_, j), &b) in rhs.data.range((k, 0)..(k + 1, 0)) {
+                let e = acc.entry((i, j)).or_insert(self.zero);
+                *e = *e + a * b;
+            }
+        }
+
+        acc.into_iter().collect()
+    }
+
+    /// Converts the matrix into a `Dense` matrix, filling it with the stored entries.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::dense::*;
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<f32, 2, 2> = [((0, 1), 2.0), ((1, 0), 3.0)].into_iter().collect();
+    ///
+    /// let res = Dense::from_str("0 2; 3 0").unwrap();
+    /// assert_eq!(res, mat.to_dense());
+    /// ```
+    pub fn to_dense(&self) -> Dense
+    where
+        T: Into<Entry>,
+    {
+        let mut res = Dense::zeros(N, M);
+        for (&idx, &val) in self.data.iter() {
+            res[idx] = val.into();
+        }
+
+        res
+    }
+
+    /// Returns the transpose of the given matrix, keeping only the stored entries.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<i32, 2, 3> = [((0, 2), 1), ((1, 0), 2)].into_iter().collect();
+    /// let t = mat.transpose();
+    ///
+    /// assert_eq!((3, 2), t.shape());
+    /// assert_eq!(1, t[(2, 0)]);
+    /// assert_eq!(2, t[(0, 1)]);
+    /// ```
+    pub fn transpose(&self) -> Sparse<T, M, N> {
+        let data = self
+            .data
+            .iter()
+            .map(|(&(i, j), &val)| ((j, i), val))
+            .collect();
+        Sparse {
+            data,
+            zero: self.zero,
+        }
+    }
+
+    /// Formats the matrix with `precision` decimals, see `display::grid` for the layout.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<f32, 2, 2> = [((0, 0), 1234.5678), ((1, 1), -3.0)].into_iter().collect();
+    /// assert_eq!("[ 1235    0 ]\n[    0   -3 ]", mat.to_string_with(0, 4));
+    /// ```
+    pub fn to_string_with(&self, precision: usize, width: usize) -> String {
+        grid(N, M, width, |i, j| format!("{:.precision$}", self[(i, j)]))
+    }
+
+    /// Computes the sum of the entries in the diagonal of the matrix.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<i32, 3, 3> = [((0, 0), 2), ((1, 2), 5), ((2, 2), 3)].into_iter().collect();
+    /// assert_eq!(5, mat.trace());
+    /// ```
+    pub fn trace(&self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        self.data
+            .iter()
+            .filter(|(&(i, j), _)| i == j)
+            .fold(self.zero, |acc, (_, &e)| acc + e)
+    }
+
+    /// Returns a new matrix with the result of applying `f` to every entry,
+    /// which may have a different element type.
+    ///
+    /// # Precaution
+    ///
+    /// Like `apply`, this method will only affect entries that are already initialized.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<i32, 2, 2> = [((0, 1), 3)].into_iter().collect();
+    /// let res: Sparse<f64, 2, 2> = mat.map(|&e| e as f64 / 2.0);
+    /// assert_eq!(1.5, res[(0, 1)]);
+    /// ```
+    pub fn map<U: SparseImplTraits, F: Fn(&T) -> U>(&self, f: F) -> Sparse<U, N, M> {
+        self.data.iter().map(|(&idx, e)| (idx, f(e))).collect()
+    }
+
+    /// Computes the incomplete Cholesky factor `L` of a symmetric positive-definite matrix,
+    /// where `L` is lower triangular and has the same non-zero pattern as the lower triangle
+    /// of the matrix, so `L·Lᵀ` approximates it without any fill-in. Only the lower triangle
+    /// of the matrix is read. Returns `None` if the matrix is not square or a non-positive
+    /// pivot is found.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<f64, 2, 2> = [((0, 0), 4.0), ((1, 0), 2.0), ((0, 1), 2.0), ((1, 1), 5.0)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let l = mat.ic0().unwrap();
+    /// assert_eq!(2.0, l[(0, 0)]);
+    /// assert_eq!(1.0, l[(1, 0)]);
+    /// assert_eq!(2.0, l[(1, 1)]);
+    /// assert_eq!(0.0, l[(0, 1)]);
+    /// ```
+    pub fn ic0(&self) -> Option<Sparse<T, N, N>>
+    where
+        T: Sub<Output = T> + Div<Output = T> + PartialOrd + Sqrt,
+    {
+        if !self.is_square() {
+            return None;
+        }
+
+        // The factor is stored by columns, keyed by `(j, i)`.
+        let mut cols: BTreeMap<(usize, usize), T> = self
+            .data
+            .iter()
+            .filter(|(&(i, j), _)| i >= j)
+            .map(|(&(i, j), &val)| ((j, i), val))
+            .collect();
+
+        for k in 0..N {
+            let pivot = cols.get(&(k, k)).copied().unwrap_or(self.zero);
+            if pivot.partial_cmp(&self.zero) != Some(Ordering::Greater) {
+                return None;
+            }
+
+            let diag = pivot.sqrt();
+            cols.insert((k, k), diag);
+
+            let col: Vec<(usize, T)> = cols
+                .range((k, k + 1)..(k + 1, 0))
+                .map(|(&(_, i), &val)| (i, val / diag))
+                .collect();
+
+            for &(i, val) in &col {
+                cols.insert((k, i), val);
+            }
+
+            // Only update the entries already present in the pattern.
+            for &(j, ljk) in &col {
+                for &(i, lik) in col.iter().filter(|&&(i, _)| i >= j) {
+                    if let Some(e) = cols.get_mut(&(j, i)) {
+                        *e = *e - lik * ljk;
+                    }
+                }
+            }
+        }
+
+        Some(
+            cols.into_iter()
+                .map(|((j, i), val)| ((i, j), val))
+                .collect(),
+        )
+    }
+
+    /// Returns the quantity of non-zero entries of the product between the given matrix
+    /// and `rhs` without computing its values. An entry `(i, j)` of the product is counted
+    /// if there is some `k` for which both `(i, k)` and `(k, j)` are non-zero, so the count
+    /// is exact unless some of the products cancel each other out.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let a: Sparse<i32, 2, 3> = [((0, 1), 2), ((1, 1), 1), ((1, 2), 3)].into_iter().collect();
+    /// let b: Sparse<i32, 3, 2> = [((1, 0), 4), ((2, 1), 5)].into_iter().collect();
+    /// assert_eq!(3, a.matmul_nnz_estimate(&b));
+    /// ```
+    pub fn matmul_nnz_estimate<const K: usize>(&self, rhs: &Sparse<T, M, K>) -> usize {
+        let zero = self.zero;
+        let mut count = 0;
+        let mut cols = BTreeSet::new();
+
+        for i in 0..N {
+            cols.clear();
+            for (&(_, k), _) in self
+                .data
+                .range((i, 0)..(i + 1, 0))
+                .filter(|(_, &a)| a != zero)
+            {
+                let row = rhs.data.range((k, 0)..(k + 1, 0));
+                cols.extend(row.filter(|(_, &b)| b != zero).map(|(&(_, j), _)| j));
+            }
+
+            count += cols.len();
+        }
+
+        count
+    }
+
     /// Computes the determinant of the given matrix.
+    /// Returns `None` if the matrix is not square.
     ///
     /// # Example
     /// ```
     /// use mat_lib::sparse::Sparse;
-    /// let mat: Sparse<i32, 4, 4> = (0..4).map(|i| ((3 - i, i), i + 1)).collect();
+    /// let mat: Sparse<i32, 4, 4> = (0..4).map(|i| ((3 - i, i), i as i32 + 1)).collect();
     /// assert_eq!(Some(24), mat.det());
     /// ```
-    pub fn det(&self) -> Option<T> {
-        todo!()
+    ///
+    /// Unsigned entries are rejected at compile time, since even the determinant
+    /// of a matrix with non-negative entries can be negative.
+    /// ```compile_fail
+    /// use mat_lib::sparse::Sparse;
+    /// let mat: Sparse<u32, 2, 2> = [((0, 1), 1), ((1, 0), 1)].into_iter().collect();
+    /// mat.det();
+    /// ```
+    ///
+    /// # Algorithm
+    ///
+    /// Fraction-free (Bareiss) sparse elimination; pivots are chosen by fewest entries.
+    pub fn det(&self) -> Option<T>
+    where
+        T: Signed,
+    {
+        if !self.is_square() {
+            return None;
+        }
+
+        let zero = T::from(0);
+        let one = T::from(1);
+        if N == 0 {
+            return Some(one);
+        }
+
+        // `rows[r]` is `None` while row `r` still matches the stored row `origin[r]`.
+        // The entries of a row are current as of `base[r]`: after every step its
+        // actual values are `e * prev / base[r]`, which is always exact.
+        let mut rows: Vec<Option<BTreeMap<usize, T>>> = vec![None; N];
+        let mut origin: Vec<usize> = (0..N).collect();
+        let mut base = vec![one; N];
+
+        let stored = |i: usize| {
+            self.data
+                .range((i, 0)..(i + 1, 0))
+                .filter(move |(_, &val)| val != zero)
+        };
+
+        let entry = |row: &Option<BTreeMap<usize, T>>, origin: usize, j: usize| match row {
+            Some(row) => row.get(&j).copied(),
+            None => self
+                .data
+                .get(&(origin, j))
+                .copied()
+                .filter(|&val| val != zero),
+        };
+
+        let mut negate = false;
+        let mut prev = one;
+
+        for k in 0..N - 1 {
+            let pivot = (k..N)
+                .filter(|&r| entry(&rows[r], origin[r], k).is_some())
+                .min_by_key(|&r| match &rows[r] {
+                    Some(row) => row.len(),
+                    None => stored(origin[r]).count(),
+                });
+
+            match pivot {
+                Some(r) if r != k => {
+                    rows.swap(k, r);
+                    origin.swap(k, r);
+                    base.swap(k, r);
+                    negate = !negate;
+                }
+
+                Some(_) => {}
+                None => return Some(zero),
+            }
+
+            for r in k..N {
+                if r != k && entry(&rows[r], origin[r], k).is_none() {
+                    continue;
+                }
+
+                let row = rows[r].get_or_insert_with(|| {
+                    stored(origin[r]).map(|(&(_, j), &val)| (j, val)).collect()
+                });
+
+                if base[r] != prev {
+                    row.values_mut().for_each(|e| *e = *e * prev / base[r]);
+                    base[r] = prev;
+                }
+            }
+
+            let (upper, lower) = rows.split_at_mut(k + 1);
+            let pivot_row = upper[k].as_ref().expect("Pivot row is materialized");
+            let p = pivot_row[&k];
+
+            for (row, base) in lower.iter_mut().zip(&mut base[k + 1..]) {
+                let Some(row) = row.as_mut().filter(|row| row.contains_key(&k)) else {
+                    continue;
+                };
+
+                let f = row.remove(&k).unwrap_or(zero);
+
+                // Fill-in only happens where the pivot row has an entry.
+                for &j in pivot_row.range(k + 1..).map(|(j, _)| j) {
+                    row.entry(j).or_insert(zero);
+                }
+
+                for (&j, e) in row.iter_mut() {
+                    let a = pivot_row.get(&j).copied().unwrap_or(zero);
+                    *e = (*e * p - f * a) / prev;
+                }
+
+                row.retain(|_, e| *e != zero);
+                *base = p;
+            }
+
+            prev = p;
+        }
+
+        let last = entry(&rows[N - 1], origin[N - 1], N - 1).unwrap_or(zero);
+        let det = last * prev / base[N - 1];
+        Some(if negate { -det } else { det })
     }
 
     /// Inverts the given matrix in-place.
-    pub fn inv(&mut self) -> Option<&mut Self> {
-        todo!()
+    /// Returns `None` if the matrix is not square or is singular,
+    /// in which case the matrix is left untouched.
+    ///
+    /// # Precaution
+    ///
+    /// The inverse of a sparse matrix is often dense, so the result may store
+    /// up to `N * N` entries. The elimination runs on a temporary dense copy and
+    /// only the non-zero results are written back.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mut mat: Sparse<f32, 3, 3> = (0..3).map(|i| ((i, i), 2.0)).collect();
+    /// mat.inv();
+    ///
+    /// let res: Sparse<f32, 3, 3> = (0..3).map(|i| ((i, i), 0.5)).collect();
+    /// assert_eq!(res, mat);
+    /// ```
+    ///
+    /// Only floating point entries are supported, since the inverse of an integer
+    /// matrix generally isn't an integer matrix.
+    /// ```compile_fail
+    /// use mat_lib::sparse::Sparse;
+    /// let mut mat: Sparse<i32, 2, 2> = [((0, 0), 1), ((1, 1), 1)].into_iter().collect();
+    /// mat.inv();
+    /// ```
+    /// ```compile_fail
+    /// use mat_lib::sparse::Sparse;
+    /// let mut mat: Sparse<u32, 2, 2> = [((0, 0), 1), ((1, 1), 1)].into_iter().collect();
+    /// mat.inv();
+    /// ```
+    pub fn inv(&mut self) -> Option<&mut Self>
+    where
+        T: Float,
+    {
+        if !self.is_square() {
+            return None;
+        }
+
+        let zero = T::from(0);
+        let one = T::from(1);
+        let abs = |e: T| if e < zero { -e } else { e };
+
+        let mut rows: Vec<Vec<T>> = (0..N).map(|i| self.row(i)).collect();
+        let mut inv: Vec<Vec<T>> = (0..N)
+            .map(|i| (0..N).map(|j| if i == j { one } else { zero }).collect())
+            .collect();
+
+        for k in 0..N {
+            let pivot = (k..N).max_by(|&a, &b| {
+                abs(rows[a][k])
+                    .partial_cmp(&abs(rows[b][k]))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+
+            if rows[pivot][k] == zero {
+                return None;
+            }
+
+            rows.swap(k, pivot);
+            inv.swap(k, pivot);
+
+            let p = rows[k][k];
+            for j in 0..N {
+                rows[k][j] = rows[k][j] / p;
+                inv[k][j] = inv[k][j] / p;
+            }
+
+            for i in (0..N).filter(|&i| i != k) {
+                let f = rows[i][k];
+                if f == zero {
+                    continue;
+                }
+
+                for j in 0..N {
+                    rows[i][j] = rows[i][j] - f * rows[k][j];
+                    inv[i][j] = inv[i][j] - f * inv[k][j];
+                }
+            }
+        }
+
+        self.data.clear();
+        for (i, row) in inv.into_iter().enumerate() {
+            for (j, val) in row.into_iter().enumerate() {
+                if val != zero {
+                    self.data.insert((i, j), val);
+                }
+            }
+        }
+
+        Some(self)
+    }
+}
+
+/// A mutable handle to an entry of a `Sparse` matrix returned by `Sparse::get_mut`.
+///
+/// The entry is written back into the matrix when the handle is dropped,
+/// removing it from the stored entries if its value is `0`.
+#[allow(private_bounds)]
+pub struct EntryMut<'a, T: SparseImplTraits> {
+    data: &'a mut BTreeMap<(usize, usize), T>,
+    idx: (usize, usize),
+    val: T,
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits> Deref for EntryMut<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.val
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits> DerefMut for EntryMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.val
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits> Drop for EntryMut<'_, T> {
+    fn drop(&mut self) {
+        if self.val == T::from(0) {
+            self.data.remove(&self.idx);
+        } else {
+            self.data.insert(self.idx, self.val);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::matrix::Matrix;
+    use std::str::FromStr;
 
     #[test]
     fn from_iter() {
@@ -165,4 +754,364 @@ mod tests {
         let mat: Sparse<f32, 3, 3> = iter.into_iter().collect();
         println!("mat:\n{mat}");
     }
+
+    #[test]
+    fn nnz() {
+        let mut mat: Sparse<i32, 4, 5> = (0..4).map(|i| ((i, i), 1)).collect();
+        assert_eq!(4, mat.nnz());
+        assert_eq!(0.2, mat.density());
+
+        let _ = &mut mat[(3, 4)];
+        assert_eq!(5, mat.nnz());
+
+        mat.prune();
+        assert_eq!(4, mat.nnz());
+        assert_eq!(Some(&0), mat.get((3, 4)));
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut mat: Sparse<i32, 3, 3> = [((1, 1), 1), ((2, 2), 1)].into_iter().collect();
+
+        let _ = mat.get_mut((0, 0));
+        assert_eq!(2, mat.nnz());
+        assert_eq!(None, mat.iter().find(|(&idx, _)| idx == (0, 0)));
+
+        mat.set((0, 0), 1);
+        let _ = mat.get_mut((0, 1));
+        assert_eq!(3, mat.nnz());
+
+        if let Some(mut num) = mat.get_mut((0, 0)) {
+            *num = 0;
+        }
+        assert_eq!(2, mat.nnz());
+
+        if let Some(mut num) = mat.get_mut((2, 0)) {
+            *num += 4;
+        }
+        assert_eq!(3, mat.nnz());
+        assert_eq!(4, mat[(2, 0)]);
+
+        mat.set((1, 1), 0);
+        assert_eq!(2, mat.nnz());
+    }
+
+    #[test]
+    fn iter() {
+        let mat: Sparse<i32, 3, 4> = [((2, 1), 3), ((0, 3), 1), ((1, 0), 2), ((0, 0), 0)]
+            .into_iter()
+            .collect();
+
+        let entries: Vec<_> = mat.iter().map(|(&idx, &val)| (idx, val)).collect();
+        assert_eq!(vec![((0, 3), 1), ((1, 0), 2), ((2, 1), 3)], entries);
+
+        let indices: Vec<_> = mat.indices().collect();
+        assert_eq!(vec![(0, 3), (1, 0), (2, 1)], indices);
+    }
+
+    #[test]
+    fn matmul() {
+        let a: Sparse<i32, 3, 4> = [((0, 0), 1), ((0, 3), 2), ((1, 1), -1), ((2, 2), 4)]
+            .into_iter()
+            .collect();
+        let b: Sparse<i32, 4, 2> = [((0, 1), 3), ((1, 0), 5), ((3, 0), 6), ((3, 1), 7)]
+            .into_iter()
+            .collect();
+
+        let res = a.matmul(&b);
+        for i in 0..3 {
+            for j in 0..2 {
+                let expected: i32 = (0..4).map(|k| a[(i, k)] * b[(k, j)]).sum();
+                assert_eq!(expected, res[(i, j)]);
+            }
+        }
+
+        assert_eq!(3, res.nnz());
+    }
+
+    #[test]
+    fn det() {
+        let mat: Sparse<i32, 4, 4> = (0..4).map(|i| ((3 - i, i), i as i32 + 1)).collect();
+        assert_eq!(Some(24), mat.det());
+
+        let mat: Sparse<i32, 3, 3> = (0..3).map(|i| ((i, i), i as i32 + 1)).collect();
+        assert_eq!(Some(6), mat.det());
+
+        let mat: Sparse<i32, 3, 3> = (0..3).map(|i| ((i, 0), i as i32 + 1)).collect();
+        assert_eq!(Some(0), mat.det());
+
+        let mat: Sparse<i32, 2, 3> = (0..2).map(|i| ((i, i), 1)).collect();
+        assert_eq!(None, mat.det());
+    }
+
+    #[test]
+    fn det_negative() {
+        let mat: Sparse<i32, 2, 2> = [((0, 0), 1), ((0, 1), 2), ((1, 0), 3), ((1, 1), 4)]
+            .into_iter()
+            .collect();
+        assert_eq!(Some(-2), mat.det());
+
+        let mat: Sparse<i32, 2, 2> = [((0, 1), 1), ((1, 0), 1)].into_iter().collect();
+        assert_eq!(Some(-1), mat.det());
+
+        let mat: Sparse<f64, 2, 2> = [((0, 1), 2.0), ((1, 0), 3.0)].into_iter().collect();
+        assert_eq!(Some(-6.0), mat.det());
+    }
+
+    #[test]
+    fn inv() {
+        let entries = [
+            ((0, 0), 2.0),
+            ((1, 1), 4.0),
+            ((1, 2), 1.0),
+            ((2, 2), 5.0),
+            ((3, 0), 1.0),
+            ((3, 3), 8.0),
+        ];
+
+        let mat: Sparse<f64, 4, 4> = entries.iter().copied().collect();
+        let mut inv: Sparse<f64, 4, 4> = entries.iter().copied().collect();
+        assert!(inv.inv().is_some());
+
+        for i in 0..4 {
+            for j in 0..4 {
+                let prod: f64 = (0..4).map(|k| mat[(i, k)] * inv[(k, j)]).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((prod - expected).abs() < 1e-12);
+            }
+        }
+
+        let mut singular: Sparse<f64, 3, 3> = (0..2).map(|i| ((i, i), 1.0)).collect();
+        assert!(singular.inv().is_none());
+
+        let mut rect: Sparse<f64, 2, 3> = (0..2).map(|i| ((i, i), 1.0)).collect();
+        assert!(rect.inv().is_none());
+    }
+
+    #[test]
+    fn to_dense() {
+        let mat: Sparse<i16, 2, 3> = [((0, 0), 1), ((0, 2), -2), ((1, 1), 3)]
+            .into_iter()
+            .collect();
+
+        let dense = mat.to_dense();
+        assert_eq!((2, 3), dense.shape());
+        assert_eq!(Dense::from_str("1 0 -2; 0 3 0").unwrap(), dense);
+    }
+
+    #[test]
+    fn transpose() {
+        let mat: Sparse<i32, 2, 3> = [((0, 0), 1), ((0, 2), 2), ((1, 1), 3)]
+            .into_iter()
+            .collect();
+
+        let t: Sparse<i32, 3, 2> = mat.transpose();
+        assert_eq!((3, 2), t.shape());
+        assert_eq!(3, t.nnz());
+        assert_eq!(1, t[(0, 0)]);
+        assert_eq!(2, t[(2, 0)]);
+        assert_eq!(3, t[(1, 1)]);
+        assert_eq!(0, t[(0, 1)]);
+
+        assert_eq!(mat, t.transpose());
+    }
+
+    #[test]
+    fn from_str() {
+        let mat = Sparse::<f32, 3, 3>::from_str("1 0 0; 0 0 2.5; 0 -3 0").unwrap();
+        assert_eq!(3, mat.nnz());
+        assert_eq!(1.0, mat[(0, 0)]);
+        assert_eq!(2.5, mat[(1, 2)]);
+        assert_eq!(-3.0, mat[(2, 1)]);
+
+        assert!(Sparse::<f32, 3, 3>::from_str("1 0; 0 1").is_err());
+        assert!(Sparse::<f32, 2, 3>::from_str("1 0; 0 1; 0 0").is_err());
+        assert!(Sparse::<i32, 1, 2>::from_str("1 a").is_err());
+    }
+
+    #[test]
+    fn trace() {
+        let mat: Sparse<i32, 5, 5> = (0..5).map(|i| ((i, i), 1)).collect();
+        assert_eq!(5, mat.trace());
+
+        let mat: Sparse<f64, 3, 4> = [((0, 0), 1.5), ((0, 1), 7.0), ((2, 2), 2.5)]
+            .into_iter()
+            .collect();
+        assert_eq!(4.0, mat.trace());
+    }
+
+    #[test]
+    fn map() {
+        let mat: Sparse<i32, 3, 3> = [((0, 0), 1), ((1, 2), -4), ((2, 1), 7)]
+            .into_iter()
+            .collect();
+
+        let res: Sparse<f64, 3, 3> = mat.map(|&x| x as f64 * 0.5);
+        assert_eq!(3, res.nnz());
+        assert_eq!(0.5, res[(0, 0)]);
+        assert_eq!(-2.0, res[(1, 2)]);
+        assert_eq!(3.5, res[(2, 1)]);
+        assert_eq!(0.0, res[(1, 1)]);
+    }
+
+    #[test]
+    fn apply_indexed() {
+        let mut mat = Sparse::<i32, 3, 3>::from_str("1 2 3; 4 5 6; 7 8 9").unwrap();
+        mat.apply_indexed(|(i, j), v| if i > j { 0 } else { v });
+        mat.prune();
+
+        let res = Sparse::<i32, 3, 3>::from_str("1 2 3; 0 5 6; 0 0 9").unwrap();
+        assert_eq!(res, mat);
+
+        let mut mat: Sparse<i32, 3, 3> = [((2, 1), 1)].into_iter().collect();
+        mat.apply_indexed(|(i, j), _| (i * 10 + j) as i32);
+        assert_eq!(1, mat.nnz());
+        assert_eq!(21, mat[(2, 1)]);
+    }
+
+    #[test]
+    fn clone() {
+        let a: Sparse<i32, 3, 3> = (0..3).map(|i| ((i, i), 1)).collect();
+        let mut b = a.clone();
+        b.scalar_mul(3);
+
+        assert_eq!(1, a[(1, 1)]);
+        assert_eq!(3, b[(1, 1)]);
+    }
+
+    #[test]
+    fn ic0() {
+        // A tridiagonal matrix has no fill-in, so the factor is exact.
+        let mut mat = Sparse::<f64, 4, 4>::zeros();
+        for i in 0..4 {
+            mat.set((i, i), 4.0);
+            if i > 0 {
+                mat.set((i, i - 1), -1.0);
+                mat.set((i - 1, i), -1.0);
+            }
+        }
+
+        let l = mat.ic0().unwrap();
+        let pattern: Vec<_> = l.indices().collect();
+        let lower: Vec<_> = mat.indices().filter(|&(i, j)| i >= j).collect();
+        assert_eq!(lower, pattern);
+
+        let res = l.matmul(&l.transpose());
+        assert!(mat.indices().all(|idx| (res[idx] - mat[idx]).abs() < 1e-12));
+
+        // The arrow pattern drops the fill-in at (2, 1) but keeps the pattern.
+        let mat: Sparse<f64, 3, 3> = [
+            ((0, 0), 4.0),
+            ((1, 1), 4.0),
+            ((2, 2), 4.0),
+            ((1, 0), 1.0),
+            ((0, 1), 1.0),
+            ((2, 0), 1.0),
+            ((0, 2), 1.0),
+        ]
+        .into_iter()
+        .collect();
+        let l = mat.ic0().unwrap();
+        assert_eq!(None, l.get((2, 1)).filter(|&&x| x != 0.0));
+        let res = l.matmul(&l.transpose());
+        assert!(mat.indices().all(|idx| (res[idx] - mat[idx]).abs() < 1e-12));
+
+        let indefinite: Sparse<f64, 2, 2> = [((0, 0), 1.0), ((1, 0), 2.0), ((1, 1), 1.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(None, indefinite.ic0());
+        assert_eq!(None, Sparse::<f32, 2, 3>::zeros().ic0());
+    }
+
+    #[test]
+    fn det_sparse_lu() {
+        // A 12 x 12 matrix with a tridiagonal band plus a few scattered entries.
+        let mut mat = Sparse::<i64, 12, 12>::zeros();
+        for i in 0..12 {
+            mat.set((i, i), 4 + (i as i64 % 3));
+            if i > 0 {
+                mat.set((i, i - 1), -1);
+                mat.set((i - 1, i), 2);
+            }
+        }
+        mat.set((0, 11), 3);
+        mat.set((7, 2), -2);
+        mat.set((11, 5), 1);
+
+        let expected = mat.map(|&x| x as f32).to_dense().det().unwrap();
+        let det = mat.det().unwrap();
+        assert!((det as f64 - expected as f64).abs() <= expected.abs() as f64 * 1e-4);
+
+        let floats = mat.map(|&x| x as f64 / 2.0);
+        let expected = floats.map(|&x| x as f32).to_dense().det().unwrap();
+        let det = floats.det().unwrap();
+        assert!((det - expected as f64).abs() <= expected.abs() as f64 * 1e-4);
+
+        // Integer results are exact.
+        let mat: Sparse<i32, 2, 2> = [((0, 0), 2), ((0, 1), 1), ((1, 0), 1), ((1, 1), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(Some(3), mat.det());
+    }
+
+    #[test]
+    fn eq_ignores_stored_zeros() {
+        let untouched = Sparse::<i32, 2, 2>::zeros();
+
+        let mut mat = Sparse::<i32, 2, 2>::zeros();
+        mat.set((0, 0), 5);
+        mat.set((0, 0), 0);
+        assert_eq!(untouched, mat);
+
+        // Writing through `IndexMut` stores the entry even if it ends up being zero.
+        mat[(1, 0)] = 5;
+        mat[(1, 0)] = 0;
+        assert_eq!(1, mat.nnz());
+        assert_eq!(untouched, mat);
+        assert_eq!(mat, untouched);
+
+        mat[(1, 1)] = 2;
+        assert_ne!(untouched, mat);
+        assert_ne!(mat, untouched);
+    }
+
+    #[test]
+    fn matmul_nnz_estimate() {
+        let a: Sparse<u32, 4, 5> = [
+            ((0, 0), 1),
+            ((0, 3), 2),
+            ((1, 4), 3),
+            ((3, 1), 4),
+            ((3, 3), 5),
+        ]
+        .into_iter()
+        .collect();
+        let b: Sparse<u32, 5, 3> = [
+            ((0, 0), 1),
+            ((0, 2), 2),
+            ((1, 1), 3),
+            ((3, 0), 4),
+            ((3, 1), 5),
+        ]
+        .into_iter()
+        .collect();
+
+        let estimate = a.matmul_nnz_estimate(&b);
+        assert_eq!(a.matmul(&b).nnz(), estimate);
+        assert_eq!(5, estimate);
+
+        assert_eq!(0, Sparse::<u32, 4, 5>::zeros().matmul_nnz_estimate(&b));
+    }
+
+    #[test]
+    fn matrix_get_mut() {
+        let mut mat: Sparse<i32, 3, 3> = [((1, 1), 1)].into_iter().collect();
+
+        let _ = Matrix::get_mut(&mut mat, (0, 0));
+        assert_eq!(2, mat.nnz());
+        assert_eq!(Sparse::from_iter([((1, 1), 1)]), mat);
+
+        mat.prune();
+        assert_eq!(1, mat.nnz());
+    }
 }
@@ -1,14 +1,30 @@
+mod csr;
+mod market;
 mod mat;
+#[cfg(feature = "serde")]
+mod serialize;
 mod traits;
 
-use std::{fmt::Display, ops::Mul};
+use std::fmt::Display;
+use std::ops::{Div, Mul, Neg, Sub};
 
+pub use csr::*;
 pub use mat::*;
 
-trait SparseImplTraits: From<u8> + Copy + Mul<Output = Self> + Display {}
+pub(crate) trait SparseImplTraits:
+    From<u8> + Copy + Mul<Output = Self> + PartialEq + Display
+{
+    /// Whether the type only holds integral values, `false` for floating point types.
+    const INTEGER: bool = true;
+}
 
-impl SparseImplTraits for f64 {}
-impl SparseImplTraits for f32 {}
+impl SparseImplTraits for f64 {
+    const INTEGER: bool = false;
+}
+
+impl SparseImplTraits for f32 {
+    const INTEGER: bool = false;
+}
 
 impl SparseImplTraits for i128 {}
 impl SparseImplTraits for i64 {}
@@ -20,3 +36,39 @@ impl SparseImplTraits for u64 {}
 impl SparseImplTraits for u32 {}
 impl SparseImplTraits for u16 {}
 impl SparseImplTraits for u8 {}
+
+/// Entry types with a square root, required by factorizations such as `ic0`.
+trait Sqrt {
+    fn sqrt(self) -> Self;
+}
+
+impl Sqrt for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+}
+
+impl Sqrt for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+}
+
+/// Entry types with a sign, required by eliminations such as `det` whose
+/// intermediate values can become negative.
+trait Signed: SparseImplTraits + Neg<Output = Self> + Sub<Output = Self> + Div<Output = Self> {}
+
+impl Signed for f64 {}
+impl Signed for f32 {}
+
+impl Signed for i128 {}
+impl Signed for i64 {}
+impl Signed for i32 {}
+impl Signed for i16 {}
+
+/// Floating point entry types, required by eliminations such as `inv`
+/// that need exact division.
+trait Float: Signed + PartialOrd {}
+
+impl Float for f64 {}
+impl Float for f32 {}
@@ -0,0 +1,42 @@
+//! `serde` support for `Sparse`, serialized as its shape and stored entries.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::mat::Sparse;
+use super::SparseImplTraits;
+
+#[derive(Serialize, Deserialize)]
+struct SparseRepr<T> {
+    shape: (usize, usize),
+    entries: Vec<((usize, usize), T)>,
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits + Serialize, const N: usize, const M: usize> Serialize
+    for Sparse<T, N, M>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries = self.iter().map(|(&idx, &val)| (idx, val)).collect();
+        let shape = (N, M);
+        SparseRepr { shape, entries }.serialize(serializer)
+    }
+}
+
+#[allow(private_bounds)]
+impl<'de, T: SparseImplTraits + Deserialize<'de>, const N: usize, const M: usize> Deserialize<'de>
+    for Sparse<T, N, M>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SparseRepr::<T>::deserialize(deserializer)?;
+        if repr.shape != (N, M) {
+            return Err(serde::de::Error::custom("Invalid shape for matrix"));
+        }
+
+        let mut mat = Self::zeros();
+        for (idx, val) in repr.entries {
+            mat.set(idx, val)
+                .ok_or_else(|| serde::de::Error::custom("Index out of range"))?;
+        }
+
+        Ok(mat)
+    }
+}
@@ -1,8 +1,11 @@
 use std::fmt::{self, Display};
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
 use super::mat::Sparse;
 use super::SparseImplTraits;
+use crate::dense::parse::mat_from_str;
+use crate::matrix::Matrix;
 
 impl<T: SparseImplTraits, const N: usize, const M: usize> Index<(usize, usize)>
     for Sparse<T, N, M>
@@ -17,31 +20,31 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> IndexMut<(usize, usize
     for Sparse<T, N, M>
 {
     fn index_mut(&mut self, idx: (usize, usize)) -> &mut Self::Output {
-        self.get_mut(idx).expect("IndexMut out of range")
+        self.entry_mut(idx).expect("IndexMut out of range")
     }
 }
 
 impl<T: SparseImplTraits, const N: usize, const M: usize> Display for Sparse<T, N, M> {
+    /// Formats the matrix as `to_string_with(4, 0)`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (n, m) = self.shape();
-        let mut rows = Vec::with_capacity(n);
-
-        let len = 7;
-        for i in 0..n {
-            let mut row = String::with_capacity(len * m + 3);
-            row.push('[');
-
-            for j in 0..m {
-                let num = self[(i, j)];
-                let fmt = format!(" {num:.4}");
-                row.push_str(&fmt[..len.min(fmt.len())]);
-            }
-
-            row.push_str(" ]");
-            rows.push(row);
-        }
+        write!(f, "{}", self.to_string_with(4, 0))
+    }
+}
 
-        write!(f, "{}", rows.join("\n"))
+impl<T: SparseImplTraits, const N: usize, const M: usize> PartialEq for Sparse<T, N, M> {
+    /// Two matrices are equal if every pair of corresponding entries is equal,
+    /// so an entry storing `0` is equal to a missing one.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// let mut a = Sparse::<i32, 2, 2>::zeros();
+    /// a[(0, 0)] = 0;
+    /// assert_eq!(Sparse::zeros(), a);
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().all(|(&idx, val)| other[idx] == *val)
+            && other.iter().all(|(&idx, val)| self[idx] == *val)
     }
 }
 
@@ -58,3 +61,68 @@ impl<T: SparseImplTraits, const N: usize, const M: usize> FromIterator<((usize,
         mat
     }
 }
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits, const N: usize, const M: usize> Matrix<T> for Sparse<T, N, M> {
+    /// The shape of a `Sparse` is fixed by `N` and `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(n, m)` differs from `(N, M)`.
+    fn zeros(n: usize, m: usize) -> Self {
+        assert_eq!((N, M), (n, m), "Shape does not match the matrix dimensions");
+        Sparse::zeros()
+    }
+
+    fn get(&self, idx: (usize, usize)) -> Option<&T> {
+        Sparse::get(self, idx)
+    }
+
+    /// The trait requires a plain reference, so a missing entry is stored as `0`
+    /// before being returned, the same as with `IndexMut`. Stored zeros don't change
+    /// the value of the matrix but count towards `nnz` until `prune` is called.
+    /// Prefer the inherent `Sparse::get_mut`, which never stores a `0`.
+    fn get_mut(&mut self, idx: (usize, usize)) -> Option<&mut T> {
+        self.entry_mut(idx)
+    }
+
+    fn set(&mut self, idx: (usize, usize), val: T) -> Option<T> {
+        Sparse::set(self, idx, val)
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        Sparse::shape(self)
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: SparseImplTraits + FromStr, const N: usize, const M: usize> FromStr for Sparse<T, N, M> {
+    type Err = &'static str;
+    /// Create a `Sparse` matrix from a string using the same syntax as `Dense`.
+    /// Only the non-zero entries are stored.
+    ///
+    /// # Usage
+    /// ```
+    /// use mat_lib::sparse::Sparse;
+    /// use std::str::FromStr;
+    ///
+    /// let mat = Sparse::<i32, 2, 3>::from_str("1 0 0; 0 0 2").unwrap();
+    /// assert_eq!(2, mat.nnz());
+    /// assert_eq!(2, mat[(1, 2)]);
+    ///
+    /// // Shape doesn't match `N x M`.
+    /// assert!(Sparse::<i32, 3, 2>::from_str("1 0 0; 0 0 2").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (data, n, m) = mat_from_str::<T>(s, ";")?;
+        if (n, m) != (N, M) {
+            return Err("Invalid shape for matrix");
+        }
+
+        let iter = data
+            .into_iter()
+            .enumerate()
+            .map(|(k, val)| ((k / M, k % M), val));
+        Ok(iter.collect())
+    }
+}
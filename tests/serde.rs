@@ -0,0 +1,54 @@
+//! Round trips through `serde_json`. They live outside of the crate so that
+//! `serde_json`'s `PartialEq` impls for floats don't leak into the unit tests.
+#![cfg(feature = "serde")]
+
+use mat_lib::dense::DenseF32;
+use mat_lib::diag::Diag;
+use mat_lib::sparse::Sparse;
+
+#[test]
+fn dense_round_trip() {
+    let mat = DenseF32::from_str("1 2.5 3; -4 5 6e3").unwrap();
+    let json = serde_json::to_string(&mat).unwrap();
+    assert_eq!(mat, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn dense_invalid_shape() {
+    let json = r#"{"shape":[2,2],"data":[1.0,2.0,3.0]}"#;
+    assert!(serde_json::from_str::<DenseF32>(json).is_err());
+}
+
+#[test]
+fn sparse_round_trip() {
+    let mat: Sparse<i32, 3, 4> = [((0, 1), 5), ((2, 3), -7)].into_iter().collect();
+    let json = serde_json::to_string(&mat).unwrap();
+    assert_eq!(r#"{"shape":[3,4],"entries":[[[0,1],5],[[2,3],-7]]}"#, json);
+    assert_eq!(mat, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn sparse_invalid() {
+    let json = r#"{"shape":[3,3],"entries":[]}"#;
+    assert!(serde_json::from_str::<Sparse<i32, 3, 4>>(json).is_err());
+
+    let json = r#"{"shape":[3,4],"entries":[[[3,0],1]]}"#;
+    assert!(serde_json::from_str::<Sparse<i32, 3, 4>>(json).is_err());
+}
+
+#[test]
+fn diag_round_trip() {
+    let mat = Diag::<f64, 2, 3>::from([1.5, -2.0]).unwrap();
+    let json = serde_json::to_string(&mat).unwrap();
+    assert_eq!(r#"{"shape":[2,3],"diagonal":[1.5,-2.0]}"#, json);
+    assert_eq!(mat, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn diag_invalid() {
+    let json = r#"{"shape":[2,3],"diagonal":[1.0]}"#;
+    assert!(serde_json::from_str::<Diag<f64, 2, 3>>(json).is_err());
+
+    let json = r#"{"shape":[3,2],"diagonal":[1.0,2.0]}"#;
+    assert!(serde_json::from_str::<Diag<f64, 2, 3>>(json).is_err());
+}